@@ -0,0 +1,489 @@
+//! Append-only arenas whose handles are branded with the arena they came
+//! from.
+//!
+//! Since an arena never shrinks, a handle that was once valid remains valid
+//! for as long as the arena exists, so lookups need no bounds checks.
+//! Handles from one arena cannot be used with another, because every arena
+//! is created with a fresh invariant lifetime.
+
+use std::{fmt, slice, vec};
+use std::iter::Enumerate;
+use std::ops::{Index, IndexMut};
+use std::marker::PhantomData;
+use super::PhantomInvariantLifetime;
+
+/// A handle to an element of an `Arena<'a, T>`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle<'a> {
+    brand: PhantomInvariantLifetime<'a>,
+    index: usize,
+}
+
+impl<'a> Handle<'a> {
+    unsafe fn from_raw(index: usize) -> Self {
+        Handle { brand: PhantomData, index }
+    }
+
+    /// The position of the element in order of allocation.
+    pub fn index(self) -> usize {
+        self.index
+    }
+}
+
+impl<'a> fmt::Debug for Handle<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Handle(")?;
+        self.index.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+/// An append-only collection of `T` with brand `'a`.
+///
+/// Handles can't be used across arenas:
+///
+/// ```compile_fail
+/// use imprint::arena::Arena;
+///
+/// Arena::scope(|mut a| Arena::scope(|mut b| {
+///     let h = a.alloc(1);
+///     b.alloc(2);
+///     b[h]
+/// }));
+/// ```
+pub struct Arena<'a, T> {
+    brand: PhantomInvariantLifetime<'a>,
+    items: Vec<T>,
+}
+
+impl<'a, T> Arena<'a, T> {
+    /// Creates an empty arena with a fresh brand.
+    pub fn scope<F, R>(callback: F) -> R
+        where F: for<'b> FnOnce(Arena<'b, T>) -> R {
+        callback(Arena { brand: PhantomData, items: Vec::new() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn alloc(&mut self, value: T) -> Handle<'a> {
+        let index = self.items.len();
+        self.items.push(value);
+        unsafe { Handle::from_raw(index) }
+    }
+
+    /// Handles of all elements in order of allocation.
+    pub fn handles(&self) -> Handles<'a> {
+        Handles { brand: PhantomData, range: 0 .. self.items.len() }
+    }
+
+    pub fn iter<'b>(&'b self) -> Iter<'b, 'a, T> {
+        Iter { brand: PhantomData, inner: self.items.iter().enumerate() }
+    }
+
+    pub fn iter_mut<'b>(&'b mut self) -> IterMut<'b, 'a, T> {
+        IterMut {
+            brand: PhantomData,
+            inner: self.items.iter_mut().enumerate(),
+        }
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Arena<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Arena(")?;
+        self.items.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, T> Index<Handle<'a>> for Arena<'a, T> {
+    type Output = T;
+    fn index(&self, handle: Handle<'a>) -> &Self::Output {
+        unsafe { self.items.get_unchecked(handle.index) }
+    }
+}
+
+impl<'a, T> IndexMut<Handle<'a>> for Arena<'a, T> {
+    fn index_mut(&mut self, handle: Handle<'a>) -> &mut Self::Output {
+        unsafe { self.items.get_unchecked_mut(handle.index) }
+    }
+}
+
+impl<'a, T> IntoIterator for Arena<'a, T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'b, 'a, T> IntoIterator for &'b Arena<'a, T> {
+    type Item = (Handle<'a>, &'b T);
+    type IntoIter = Iter<'b, 'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the handles of an `Arena`.
+#[derive(Clone, Debug)]
+pub struct Handles<'a> {
+    brand: PhantomInvariantLifetime<'a>,
+    range: ::std::ops::Range<usize>,
+}
+
+impl<'a> Iterator for Handles<'a> {
+    type Item = Handle<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| unsafe { Handle::from_raw(i) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Handles<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| unsafe { Handle::from_raw(i) })
+    }
+}
+
+impl<'a> ExactSizeIterator for Handles<'a> {}
+
+/// An iterator over the elements of an `Arena` along with their handles.
+pub struct Iter<'b, 'a, T: 'b> {
+    brand: PhantomInvariantLifetime<'a>,
+    inner: Enumerate<slice::Iter<'b, T>>,
+}
+
+impl<'b, 'a, T> Clone for Iter<'b, 'a, T> {
+    fn clone(&self) -> Self {
+        Iter { brand: PhantomData, inner: self.inner.clone() }
+    }
+}
+
+impl<'b, 'a, T> Iterator for Iter<'b, 'a, T> {
+    type Item = (Handle<'a>, &'b T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, x)| (unsafe { Handle::from_raw(i) }, x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'b, 'a, T> DoubleEndedIterator for Iter<'b, 'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+            .map(|(i, x)| (unsafe { Handle::from_raw(i) }, x))
+    }
+}
+
+impl<'b, 'a, T> ExactSizeIterator for Iter<'b, 'a, T> {}
+
+/// A mutable variant of `Iter`.
+pub struct IterMut<'b, 'a, T: 'b> {
+    brand: PhantomInvariantLifetime<'a>,
+    inner: Enumerate<slice::IterMut<'b, T>>,
+}
+
+impl<'b, 'a, T> Iterator for IterMut<'b, 'a, T> {
+    type Item = (Handle<'a>, &'b mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, x)| (unsafe { Handle::from_raw(i) }, x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'b, 'a, T> DoubleEndedIterator for IterMut<'b, 'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+            .map(|(i, x)| (unsafe { Handle::from_raw(i) }, x))
+    }
+}
+
+impl<'b, 'a, T> ExactSizeIterator for IterMut<'b, 'a, T> {}
+
+/// Defines a newtype over `Handle` along with an iterator over a range of
+/// them, so that handles into different arenas of a structure have
+/// distinct types.
+macro_rules! handle_newtype {
+    ($(#[$attr:meta])* $id:ident, $(#[$iter_attr:meta])* $ids:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $id<'a>(Handle<'a>);
+
+        impl<'a> $id<'a> {
+            pub fn index(self) -> usize {
+                self.0.index()
+            }
+        }
+
+        impl<'a> fmt::Debug for $id<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(concat!(stringify!($id), "("))?;
+                self.0.index.fmt(f)?;
+                f.write_str(")")
+            }
+        }
+
+        $(#[$iter_attr])*
+        #[derive(Clone, Debug)]
+        pub struct $ids<'a>(Handles<'a>);
+
+        impl<'a> Iterator for $ids<'a> {
+            type Item = $id<'a>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next().map($id)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+        }
+
+        impl<'a> DoubleEndedIterator for $ids<'a> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.0.next_back().map($id)
+            }
+        }
+
+        impl<'a> ExactSizeIterator for $ids<'a> {}
+    }
+}
+
+handle_newtype! {
+    /// Identifies a node of a `Graph<'n, 'e, N, E>`.
+    NodeId,
+    /// An iterator over the nodes of a `Graph`.
+    NodeIds
+}
+
+handle_newtype! {
+    /// Identifies an edge of a `Graph<'n, 'e, N, E>`.
+    EdgeId,
+    /// An iterator over the edges of a `Graph`.
+    EdgeIds
+}
+
+struct Node<'e, N> {
+    value: N,
+    out_edges: Vec<EdgeId<'e>>,
+}
+
+struct Edge<'n, E> {
+    value: E,
+    source: NodeId<'n>,
+    target: NodeId<'n>,
+}
+
+/// A directed graph stored as adjacency lists, with nodes of type `N` and
+/// edges of type `E`, branded with `'n` and `'e` respectively.
+///
+/// Nodes and edges are kept in separate arenas, each with its own brand.
+/// Like any arena, they can only grow, so their ids never dangle.
+///
+/// Ids can't be used across graphs:
+///
+/// ```compile_fail
+/// use imprint::arena::Graph;
+///
+/// Graph::<_, ()>::scope(|mut a| Graph::<_, ()>::scope(|mut b| {
+///     let n = a.add_node(1);
+///     b.add_node(2);
+///     b[n]
+/// }));
+/// ```
+pub struct Graph<'n, 'e, N, E> {
+    nodes: Arena<'n, Node<'e, N>>,
+    edges: Arena<'e, Edge<'n, E>>,
+}
+
+impl<'n, 'e, N, E> Graph<'n, 'e, N, E> {
+    /// Creates an empty graph with fresh brands.
+    pub fn scope<F, R>(callback: F) -> R
+        where F: for<'m, 'f> FnOnce(Graph<'m, 'f, N, E>) -> R {
+        callback(Graph {
+            nodes: Arena { brand: PhantomData, items: Vec::new() },
+            edges: Arena { brand: PhantomData, items: Vec::new() },
+        })
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_node(&mut self, value: N) -> NodeId<'n> {
+        NodeId(self.nodes.alloc(Node { value, out_edges: Vec::new() }))
+    }
+
+    pub fn add_edge(&mut self, source: NodeId<'n>, target: NodeId<'n>,
+                    value: E) -> EdgeId<'e> {
+        let edge = EdgeId(self.edges.alloc(Edge { value, source, target }));
+        self.nodes[source.0].out_edges.push(edge);
+        edge
+    }
+
+    /// Returns the source and target of an edge.
+    pub fn endpoints(&self, edge: EdgeId<'e>) -> (NodeId<'n>, NodeId<'n>) {
+        let edge = &self.edges[edge.0];
+        (edge.source, edge.target)
+    }
+
+    pub fn nodes(&self) -> NodeIds<'n> {
+        NodeIds(self.nodes.handles())
+    }
+
+    pub fn edges(&self) -> EdgeIds<'e> {
+        EdgeIds(self.edges.handles())
+    }
+
+    /// The edges leaving `node`, in order of insertion.
+    pub fn out_edges(&self, node: NodeId<'n>) -> &[EdgeId<'e>] {
+        &self.nodes[node.0].out_edges
+    }
+
+    /// The targets of the edges leaving `node`.
+    pub fn successors<'b>(&'b self, node: NodeId<'n>)
+                          -> Successors<'b, 'n, 'e, N, E> {
+        Successors { graph: self, inner: self.out_edges(node).iter() }
+    }
+}
+
+impl<'n, 'e, N: fmt::Debug, E: fmt::Debug> fmt::Debug
+    for Graph<'n, 'e, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Graph(")?;
+        f.debug_list()
+            .entries(self.nodes.iter().map(|(_, node)| &node.value))
+            .finish()?;
+        f.write_str(", ")?;
+        f.debug_list()
+            .entries(self.edges.iter().map(|(_, edge)| {
+                (edge.source, edge.target, &edge.value)
+            }))
+            .finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'n, 'e, N, E> Index<NodeId<'n>> for Graph<'n, 'e, N, E> {
+    type Output = N;
+    fn index(&self, node: NodeId<'n>) -> &Self::Output {
+        &self.nodes[node.0].value
+    }
+}
+
+impl<'n, 'e, N, E> IndexMut<NodeId<'n>> for Graph<'n, 'e, N, E> {
+    fn index_mut(&mut self, node: NodeId<'n>) -> &mut Self::Output {
+        &mut self.nodes[node.0].value
+    }
+}
+
+impl<'n, 'e, N, E> Index<EdgeId<'e>> for Graph<'n, 'e, N, E> {
+    type Output = E;
+    fn index(&self, edge: EdgeId<'e>) -> &Self::Output {
+        &self.edges[edge.0].value
+    }
+}
+
+impl<'n, 'e, N, E> IndexMut<EdgeId<'e>> for Graph<'n, 'e, N, E> {
+    fn index_mut(&mut self, edge: EdgeId<'e>) -> &mut Self::Output {
+        &mut self.edges[edge.0].value
+    }
+}
+
+/// An iterator over the successors of a node in a `Graph`.
+pub struct Successors<'b, 'n: 'b, 'e: 'b, N: 'b, E: 'b> {
+    graph: &'b Graph<'n, 'e, N, E>,
+    inner: slice::Iter<'b, EdgeId<'e>>,
+}
+
+impl<'b, 'n, 'e, N, E> Iterator for Successors<'b, 'n, 'e, N, E> {
+    type Item = NodeId<'n>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|&edge| self.graph.endpoints(edge).1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'b, 'n, 'e, N, E> ExactSizeIterator for Successors<'b, 'n, 'e, N, E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arena() {
+        Arena::scope(|mut arena| {
+            let a = arena.alloc("a");
+            let b = arena.alloc("b");
+            arena[a] = "c";
+            assert_eq!(arena.len(), 2);
+            assert_eq!(arena[b], "b");
+            assert_eq!(format!("{:?}", arena.handles().collect::<Vec<_>>()),
+                       "[Handle(0), Handle(1)]");
+            for (_, x) in arena.iter_mut() {
+                *x = "d";
+            }
+            assert_eq!(arena.into_vec(), ["d", "d"]);
+        })
+    }
+
+    #[test]
+    fn graph() {
+        Graph::scope(|mut g| {
+            let a = g.add_node("a");
+            let b = g.add_node("b");
+            let c = g.add_node("c");
+            let ab = g.add_edge(a, b, 1);
+            g.add_edge(a, c, 2);
+            g.add_edge(b, c, 3);
+            assert_eq!(g.node_count(), 3);
+            assert_eq!(g.edge_count(), 3);
+            assert_eq!(g.endpoints(ab), (a, b));
+            assert_eq!(g.successors(a).collect::<Vec<_>>(), [b, c]);
+            g[ab] += 10;
+            let total: i32 = g.edges().map(|e| g[e]).sum();
+            assert_eq!(total, 16);
+            let names: String = g.nodes().map(|n| g[n]).collect();
+            assert_eq!(names, "abc");
+            assert_eq!(format!("{:?}", g),
+                       "Graph([\"a\", \"b\", \"c\"], \
+                        [(NodeId(0), NodeId(1), 11), \
+                        (NodeId(0), NodeId(2), 2), \
+                        (NodeId(1), NodeId(2), 3)])");
+        })
+    }
+}
@@ -15,9 +15,10 @@
 //!
 //! See [`Val`](../struct.Val.html#properties) for more info.
 
-use std::fmt;
+use std::{cmp, fmt};
 use std::marker::PhantomData;
-use num::Integer;
+use std::rc::Rc;
+use num::{self, CheckedAdd, CheckedMul, Integer, Unsigned};
 use super::{PhantomInvariantType, TyEq, Val, Value, imprint};
 
 macro_rules! impl_all1 {
@@ -64,15 +65,283 @@ macro_rules! impl_all2 {
     }
 }
 
+macro_rules! impl_all3 {
+    ( $name:ident ) => {
+        impl<X: ?Sized, Y: ?Sized, Z: ?Sized> $name<X, Y, Z> {
+            pub unsafe fn conjure() -> Self {
+                $name(PhantomData, PhantomData, PhantomData)
+            }
+        }
+
+        // shut up clippy: we don't want Clone constraints on X, Y, or Z
+        #[cfg_attr(feature = "cargo-clippy", allow(expl_impl_clone_on_copy))]
+        impl<X: ?Sized, Y: ?Sized, Z: ?Sized> Clone for $name<X, Y, Z> {
+            fn clone(&self) -> Self { *self }
+        }
+
+        impl<X: ?Sized, Y: ?Sized, Z: ?Sized> Copy for $name<X, Y, Z> { }
+
+        impl<X: ?Sized, Y: ?Sized, Z: ?Sized> fmt::Debug for $name<X, Y, Z> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(stringify!($name))
+            }
+        }
+    }
+}
+
 /// Negation.
 pub struct Not<P: ?Sized>(PhantomInvariantType<P>);
 
 impl<P> Not<P> {
     pub fn absurd(_: P) -> ! { panic!() }
+
+    /// `¬P -> (P -> Q)`
+    pub fn into_implies<'a, Q>(self) -> Implies<'a, P, Q>
+        where P: 'a, Q: 'a {
+        Implies::new(|p| Not::absurd(p))
+    }
+}
+
+impl<P, Q> Not<Or<P, Q>> {
+    /// `¬(P ∨ Q) -> ¬P ∧ ¬Q`
+    pub fn demorgan(self) -> And<Not<P>, Not<Q>> {
+        And(unsafe { Not::conjure() }, unsafe { Not::conjure() })
+    }
 }
 
 impl_all1!(Not);
 
+/// Conjunction.
+#[derive(Clone, Copy, Debug)]
+pub struct And<P, Q>(pub P, pub Q);
+
+impl<P, Q> And<P, Q> {
+    /// `P ∧ Q -> P`
+    pub fn left(self) -> P {
+        self.0
+    }
+
+    /// `P ∧ Q -> Q`
+    pub fn right(self) -> Q {
+        self.1
+    }
+
+    /// `P ∧ Q -> Q ∧ P`
+    pub fn comm(self) -> And<Q, P> {
+        And(self.1, self.0)
+    }
+}
+
+impl<P, Q> And<Not<P>, Not<Q>> {
+    /// `¬P ∧ ¬Q -> ¬(P ∨ Q)`
+    pub fn demorgan(self) -> Not<Or<P, Q>> {
+        unsafe { Not::conjure() }
+    }
+}
+
+/// Disjunction.
+///
+/// Note that the dual De Morgan law, `¬(P ∧ Q) -> ¬P ∨ ¬Q`, is not
+/// provided: it is not valid constructively, as it would have to somehow
+/// pick a side.  If both `P` and `Q` are decidable, decide one of them
+/// instead.
+#[derive(Clone, Copy, Debug)]
+pub enum Or<P, Q> {
+    Left(P),
+    Right(Q),
+}
+
+impl<P, Q> Or<P, Q> {
+    /// `(P ∨ Q, P -> R, Q -> R) -> R`
+    pub fn elim<R, F, G>(self, left: F, right: G) -> R
+        where F: FnOnce(P) -> R, G: FnOnce(Q) -> R {
+        match self {
+            Or::Left(p) => left(p),
+            Or::Right(q) => right(q),
+        }
+    }
+
+    /// `P ∨ Q -> Q ∨ P`
+    pub fn comm(self) -> Or<Q, P> {
+        self.elim(Or::Right, Or::Left)
+    }
+
+    /// `(P ∨ Q, ¬P) -> Q`
+    pub fn resolve_left(self, _: Not<P>) -> Q {
+        match self {
+            Or::Left(p) => Not::absurd(p),
+            Or::Right(q) => q,
+        }
+    }
+
+    /// `(P ∨ Q, ¬Q) -> P`
+    pub fn resolve_right(self, _: Not<Q>) -> P {
+        match self {
+            Or::Left(p) => p,
+            Or::Right(q) => Not::absurd(q),
+        }
+    }
+
+    pub fn into_result(self) -> Result<P, Q> {
+        self.elim(Ok, Err)
+    }
+}
+
+impl<P, Q> Or<Not<P>, Not<Q>> {
+    /// `¬P ∨ ¬Q -> ¬(P ∧ Q)`
+    pub fn demorgan(self) -> Not<And<P, Q>> {
+        unsafe { Not::conjure() }
+    }
+}
+
+impl<P, Q> From<Result<P, Q>> for Or<P, Q> {
+    fn from(result: Result<P, Q>) -> Self {
+        match result {
+            Ok(p) => Or::Left(p),
+            Err(q) => Or::Right(q),
+        }
+    }
+}
+
+/// Implication.
+///
+/// Unlike the other propositions, an implication is a *lazy* proof: it is
+/// merely a function, which could very well panic or loop forever.  Hence,
+/// it is only trustworthy once it has been applied.  For the same reason,
+/// there is no way to obtain a (strict) `Not` from an `Implies`.  See
+/// `docs/sound-logic-in-a-turing-complete-language.md` for details.
+pub struct Implies<'a, P, Q>(Rc<dyn Fn(P) -> Q + 'a>);
+
+impl<'a, P, Q> Implies<'a, P, Q> {
+    pub fn new<F: Fn(P) -> Q + 'a>(f: F) -> Self {
+        Implies(Rc::new(f))
+    }
+
+    /// `(P -> Q, P) -> Q` (modus ponens)
+    pub fn apply(&self, p: P) -> Q {
+        (self.0)(p)
+    }
+
+    /// `(P -> Q, Q -> R) -> (P -> R)`
+    pub fn trans<R>(self, other: Implies<'a, Q, R>) -> Implies<'a, P, R>
+        where P: 'a, Q: 'a, R: 'a {
+        Implies::new(move |p| other.apply(self.apply(p)))
+    }
+}
+
+impl<'a, P: 'a> Implies<'a, P, P> {
+    /// `P -> P`
+    pub fn refl() -> Self {
+        Implies::new(|p| p)
+    }
+}
+
+impl<'a, P, Q> Clone for Implies<'a, P, Q> {
+    fn clone(&self) -> Self {
+        Implies(self.0.clone())
+    }
+}
+
+impl<'a, P, Q> fmt::Debug for Implies<'a, P, Q> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Implies")
+    }
+}
+
+/// Logical equivalence.
+///
+/// Like `Implies`, this is a lazy proof.
+#[derive(Clone, Debug)]
+pub struct Iff<'a, P, Q>(pub Implies<'a, P, Q>, pub Implies<'a, Q, P>);
+
+impl<'a, P, Q> Iff<'a, P, Q> {
+    /// `(P <-> Q, P) -> Q`
+    pub fn mp(&self, p: P) -> Q {
+        self.0.apply(p)
+    }
+
+    /// `(P <-> Q, Q) -> P`
+    pub fn mpr(&self, q: Q) -> P {
+        self.1.apply(q)
+    }
+
+    /// `(P <-> Q) -> (Q <-> P)`
+    pub fn sym(self) -> Iff<'a, Q, P> {
+        Iff(self.1, self.0)
+    }
+
+    /// `(P <-> Q, Q <-> R) -> (P <-> R)`
+    pub fn trans<R>(self, other: Iff<'a, Q, R>) -> Iff<'a, P, R>
+        where P: 'a, Q: 'a, R: 'a {
+        Iff(self.0.trans(other.0), other.1.trans(self.1))
+    }
+}
+
+impl<'a, P: 'a> Iff<'a, P, P> {
+    /// `P <-> P`
+    pub fn refl() -> Self {
+        Iff(Implies::refl(), Implies::refl())
+    }
+}
+
+/// Not equal to zero.
+pub struct NonZero<X: ?Sized>(PhantomInvariantType<X>);
+
+impl<X: ?Sized, I> From<Less<Zero<I>, X>> for NonZero<X> {
+    fn from(_: Less<Zero<I>, X>) -> Self {
+        unsafe { NonZero::conjure() }
+    }
+}
+
+impl<X: ?Sized, I> From<Not<Equal<X, Zero<I>>>> for NonZero<X> {
+    fn from(_: Not<Equal<X, Zero<I>>>) -> Self {
+        unsafe { NonZero::conjure() }
+    }
+}
+
+impl<X: ?Sized> From<Positive<X>> for NonZero<X> {
+    fn from(_: Positive<X>) -> Self {
+        unsafe { NonZero::conjure() }
+    }
+}
+
+impl<'x, I> NonZero<Val<'x, I>> {
+    /// `X != 0 -> Not(X = 0)`
+    pub fn into_not_equal(self) -> Not<Equal<Val<'x, I>, Zero<I>>> {
+        unsafe { Not::conjure() }
+    }
+
+    /// `X != 0 -> 0 < X` (if `X` is unsigned)
+    pub fn into_positive(self) -> Positive<Val<'x, I>>
+        where I: Integer + Unsigned {
+        unsafe { Positive::conjure() }
+    }
+}
+
+impl_all1!(NonZero);
+
+/// Greater than zero.
+///
+/// This is equivalent to `Less<Zero<I>, X>`.  To relate it to a zero that
+/// has been imprinted as `Val<'z, I>` (see [`zero`](fn.zero.html)), use
+/// `Less::lsubst`.
+pub struct Positive<X: ?Sized>(PhantomInvariantType<X>);
+
+impl<X: ?Sized, I> From<Less<Zero<I>, X>> for Positive<X> {
+    fn from(_: Less<Zero<I>, X>) -> Self {
+        unsafe { Positive::conjure() }
+    }
+}
+
+impl<'x, I> Positive<Val<'x, I>> {
+    /// `0 < X -> Less(0, X)`
+    pub fn into_less(self) -> Less<Zero<I>, Val<'x, I>> {
+        unsafe { Less::conjure() }
+    }
+}
+
+impl_all1!(Positive);
+
 /// Equal to.
 ///
 /// This is separate from `TyEq` because `TyEq` only holds for `Val`, whereas
@@ -94,6 +363,11 @@ impl<X: ?Sized, Y: ?Sized> Equal<X, Y> {
     pub fn trans<Z: ?Sized>(self, _: Equal<Y, Z>) -> Equal<X, Z> {
         unsafe { Equal::conjure() }
     }
+
+    /// `X = Y -> ¬(X < Y)`
+    pub fn not_less(self) -> Not<Less<X, Y>> {
+        unsafe { Not::conjure() }
+    }
 }
 
 impl_all2!(Equal);
@@ -137,6 +411,35 @@ impl<X: ?Sized, Y: ?Sized> Less<X, Y> {
     pub fn lcomp_le<Z: ?Sized>(self, _: LessEqual<Z, X>) -> Less<Z, Y> {
         unsafe { Less::conjure() }
     }
+
+    /// `X < Y -> ¬(Y < X)` (asymmetry)
+    pub fn asym(self) -> Not<Less<Y, X>> {
+        unsafe { Not::conjure() }
+    }
+
+    /// `X < Y -> ¬(Y <= X)`
+    pub fn not_ge(self) -> Not<LessEqual<Y, X>> {
+        unsafe { Not::conjure() }
+    }
+
+    /// `X < Y -> ¬(X = Y)`
+    pub fn not_equal(self) -> Not<Equal<X, Y>> {
+        unsafe { Not::conjure() }
+    }
+}
+
+impl<X: ?Sized> Less<X, X> {
+    /// `¬(X < X)` (irreflexivity)
+    pub fn irrefl() -> Not<Self> {
+        unsafe { Not::conjure() }
+    }
+}
+
+impl<'x, 'y, I: Ord> Not<Less<Val<'x, I>, Val<'y, I>>> {
+    /// `¬(X < Y) -> Y <= X` (totality)
+    pub fn into_ge(self) -> LessEqual<Val<'y, I>, Val<'x, I>> {
+        unsafe { LessEqual::conjure() }
+    }
 }
 
 impl_all2!(Less);
@@ -174,6 +477,59 @@ impl<X: ?Sized, Y: ?Sized> LessEqual<X, Y> {
     pub fn comp<Z: ?Sized>(self, _: LessEqual<Y, Z>) -> LessEqual<X, Z> {
         unsafe { LessEqual::conjure() }
     }
+
+    /// `(X <= Y, Y < Z) -> X < Z`
+    pub fn rcomp_lt<Z: ?Sized>(self, _: Less<Y, Z>) -> Less<X, Z> {
+        unsafe { Less::conjure() }
+    }
+
+    /// `(X <= Y, Z < X) -> Z < Y`
+    pub fn lcomp_lt<Z: ?Sized>(self, _: Less<Z, X>) -> Less<Z, Y> {
+        unsafe { Less::conjure() }
+    }
+
+    /// `X <= Y -> ¬(Y < X)`
+    pub fn not_gt(self) -> Not<Less<Y, X>> {
+        unsafe { Not::conjure() }
+    }
+
+    /// `(X <= Y, Y <= X) -> X = Y` (antisymmetry)
+    pub fn antisym(self, _: LessEqual<Y, X>) -> Equal<X, Y> {
+        unsafe { Equal::conjure() }
+    }
+
+    /// `(X <= Y, ¬(X = Y)) -> X < Y`
+    pub fn lt_of_ne(self, _: Not<Equal<X, Y>>) -> Less<X, Y> {
+        unsafe { Less::conjure() }
+    }
+}
+
+/// `X < Y ∨ X = Y`, the case analysis of `LessEqual<X, Y>`.
+pub type LessOrEqual<X, Y> = Or<Less<X, Y>, Equal<X, Y>>;
+
+impl<X: ?Sized> LessEqual<X, X> {
+    /// `X <= X` (reflexivity)
+    pub fn refl() -> Self {
+        LessEqual::from(Equal::refl())
+    }
+}
+
+impl<'x, 'y, I: Eq> LessEqual<Val<'x, I>, Val<'y, I>> {
+    /// Determine which of `X < Y` or `X = Y` is the case.
+    pub fn split(self, x: &Val<'x, I>, y: &Val<'y, I>)
+                 -> LessOrEqual<Val<'x, I>, Val<'y, I>> {
+        match equal(x, y) {
+            Ok(eq) => Or::Right(eq),
+            Err(ne) => Or::Left(self.lt_of_ne(ne)),
+        }
+    }
+}
+
+impl<'x, 'y, I: Ord> Not<LessEqual<Val<'x, I>, Val<'y, I>>> {
+    /// `¬(X <= Y) -> Y < X` (totality)
+    pub fn into_gt(self) -> Less<Val<'y, I>, Val<'x, I>> {
+        unsafe { Less::conjure() }
+    }
 }
 
 impl_all2!(LessEqual);
@@ -184,6 +540,233 @@ pub type Greater<X, Y> = Less<Y, X>;
 /// Greater than or equal to.
 pub type GreaterEqual<X, Y> = LessEqual<Y, X>;
 
+/// The zero of `I`, promoted to the type level.
+///
+/// Unlike `Val`, a zero needs no imprinting: there is only one value of
+/// type `Zero<I>`, namely `I::zero()`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Zero<I>(I);
+
+impl<I: num::Zero> Zero<I> {
+    pub fn new() -> Self {
+        Zero(I::zero())
+    }
+}
+
+impl<I: num::Zero> Default for Zero<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<I> Value for Zero<I> {
+    type Value = I;
+    fn value(self) -> Self::Value {
+        self.0
+    }
+}
+
+unsafe impl<'a, I> Value for &'a Zero<I> {
+    type Value = &'a I;
+    fn value(self) -> Self::Value {
+        &self.0
+    }
+}
+
+/// The type-level term `X + Y`.
+///
+/// Terms have no values: they only appear as arguments of propositions
+/// such as `Equal`.
+pub struct Plus<X: ?Sized, Y: ?Sized>(
+    PhantomInvariantType<X>,
+    PhantomInvariantType<Y>,
+);
+
+/// The type-level term `X * Y`.
+///
+/// Terms have no values: they only appear as arguments of propositions
+/// such as `Equal`.
+pub struct Times<X: ?Sized, Y: ?Sized>(
+    PhantomInvariantType<X>,
+    PhantomInvariantType<Y>,
+);
+
+/// Sum: `X + Y = Z`.
+///
+/// The sum is always the mathematical one: operations that produce a `Sum`
+/// must rule out overflow.
+pub struct Sum<X: ?Sized, Y: ?Sized, Z: ?Sized>(
+    PhantomInvariantType<X>,
+    PhantomInvariantType<Y>,
+    PhantomInvariantType<Z>,
+);
+
+impl<X: ?Sized, Y: ?Sized, Z: ?Sized> Sum<X, Y, Z> {
+    /// `X + Y = Z -> Sum(X, Y, Z)`
+    pub fn from_equal(_: Equal<Plus<X, Y>, Z>) -> Self {
+        unsafe { Sum::conjure() }
+    }
+
+    /// `Sum(X, Y, Z) -> X + Y = Z`
+    pub fn into_equal(self) -> Equal<Plus<X, Y>, Z> {
+        unsafe { Equal::conjure() }
+    }
+
+    /// `X + Y = Z -> Y + X = Z`
+    pub fn comm(self) -> Sum<Y, X, Z> {
+        unsafe { Sum::conjure() }
+    }
+
+    /// `(X + Y = Z, X + Y = W) -> Z = W`
+    pub fn unique<W: ?Sized>(self, _: Sum<X, Y, W>) -> Equal<Z, W> {
+        unsafe { Equal::conjure() }
+    }
+
+    /// `(X + Y = Z, 0 < Y) -> X < Z`
+    pub fn lt_of_pos<I>(self, _: Less<Zero<I>, Y>) -> Less<X, Z> {
+        unsafe { Less::conjure() }
+    }
+
+    /// `(X + Y = Z, 0 <= Y) -> X <= Z`
+    pub fn le_of_nonneg<I>(self, _: LessEqual<Zero<I>, Y>)
+                           -> LessEqual<X, Z> {
+        unsafe { LessEqual::conjure() }
+    }
+
+    /// `(X + Y = Z, V + W = U, X <= V, Y <= W) -> Z <= U`
+    pub fn mono<V: ?Sized, W: ?Sized, U: ?Sized>(self,
+                                                 _: Sum<V, W, U>,
+                                                 _: LessEqual<X, V>,
+                                                 _: LessEqual<Y, W>)
+                                                 -> LessEqual<Z, U> {
+        unsafe { LessEqual::conjure() }
+    }
+
+    /// `X + Y = Z -> Z - Y = X`
+    pub fn into_difference(self) -> Difference<Z, Y, X> {
+        unsafe { Difference::conjure() }
+    }
+
+    /// `(X + Y = Z, V + W = U, X < V, Y <= W) -> Z < U`
+    pub fn mono_lt<V: ?Sized, W: ?Sized, U: ?Sized>(self,
+                                                    _: Sum<V, W, U>,
+                                                    _: Less<X, V>,
+                                                    _: LessEqual<Y, W>)
+                                                    -> Less<Z, U> {
+        unsafe { Less::conjure() }
+    }
+}
+
+impl_all3!(Sum);
+
+/// Difference: `X - Y = Z`.
+///
+/// As with `Sum`, the difference is always the mathematical one:
+/// operations that produce a `Difference` must rule out underflow.
+pub struct Difference<X: ?Sized, Y: ?Sized, Z: ?Sized>(
+    PhantomInvariantType<X>,
+    PhantomInvariantType<Y>,
+    PhantomInvariantType<Z>,
+);
+
+impl<X: ?Sized, Y: ?Sized, Z: ?Sized> Difference<X, Y, Z> {
+    /// `X - Y = Z -> Z + Y = X`
+    pub fn into_sum(self) -> Sum<Z, Y, X> {
+        unsafe { Sum::conjure() }
+    }
+
+    /// `X - Y = Z -> X - Z = Y`
+    pub fn swap(self) -> Difference<X, Z, Y> {
+        unsafe { Difference::conjure() }
+    }
+
+    /// `(X - Y = Z, X - Y = W) -> Z = W`
+    pub fn unique<W: ?Sized>(self, _: Difference<X, Y, W>) -> Equal<Z, W> {
+        unsafe { Equal::conjure() }
+    }
+
+    /// `(X - Y = Z, 0 < Y) -> Z < X`
+    pub fn lt_of_pos<I>(self, pos: Less<Zero<I>, Y>) -> Less<Z, X> {
+        self.into_sum().lt_of_pos(pos)
+    }
+
+    /// `(X - Y = Z, 0 <= Y) -> Z <= X`
+    pub fn le_of_nonneg<I>(self, nonneg: LessEqual<Zero<I>, Y>)
+                           -> LessEqual<Z, X> {
+        self.into_sum().le_of_nonneg(nonneg)
+    }
+
+    /// `(X - Y = Z, Y < X) -> 0 < Z`
+    pub fn pos_of_lt<I>(self, _: Less<Y, X>) -> Less<Zero<I>, Z> {
+        unsafe { Less::conjure() }
+    }
+
+    /// `(X - Y = Z, Y <= X) -> 0 <= Z`
+    pub fn nonneg_of_le<I>(self, _: LessEqual<Y, X>)
+                           -> LessEqual<Zero<I>, Z> {
+        unsafe { LessEqual::conjure() }
+    }
+}
+
+impl_all3!(Difference);
+
+/// Product: `X * Y = Z`.
+///
+/// As with `Sum`, the product is always the mathematical one: operations
+/// that produce a `Product` must rule out overflow.
+pub struct Product<X: ?Sized, Y: ?Sized, Z: ?Sized>(
+    PhantomInvariantType<X>,
+    PhantomInvariantType<Y>,
+    PhantomInvariantType<Z>,
+);
+
+impl<X: ?Sized, Y: ?Sized, Z: ?Sized> Product<X, Y, Z> {
+    /// `X * Y = Z -> Product(X, Y, Z)`
+    pub fn from_equal(_: Equal<Times<X, Y>, Z>) -> Self {
+        unsafe { Product::conjure() }
+    }
+
+    /// `Product(X, Y, Z) -> X * Y = Z`
+    pub fn into_equal(self) -> Equal<Times<X, Y>, Z> {
+        unsafe { Equal::conjure() }
+    }
+
+    /// `X * Y = Z -> Y * X = Z`
+    pub fn comm(self) -> Product<Y, X, Z> {
+        unsafe { Product::conjure() }
+    }
+
+    /// `(X * Y = Z, X * Y = W) -> Z = W`
+    pub fn unique<W: ?Sized>(self, _: Product<X, Y, W>) -> Equal<Z, W> {
+        unsafe { Equal::conjure() }
+    }
+
+    /// `(X * Y = Z, 0 <= X, 0 < Y) -> X <= Z`
+    pub fn le_of_pos<I>(self,
+                        _: LessEqual<Zero<I>, X>,
+                        _: Less<Zero<I>, Y>)
+                        -> LessEqual<X, Z> {
+        unsafe { LessEqual::conjure() }
+    }
+
+    /// `(X * Y = Z, A < X, B < Y, A * Y = P, P + B = K, 0 <= B) -> K < Z`
+    ///
+    /// This is the fact that justifies flattening a row-major index `(A,
+    /// B)` of an `X`-by-`Y` array into `A * Y + B`.
+    pub fn index_lt<A: ?Sized, B: ?Sized, P: ?Sized, K: ?Sized, I>(
+        self,
+        _: Less<A, X>,
+        _: Less<B, Y>,
+        _: Product<A, Y, P>,
+        _: Sum<P, B, K>,
+        _: LessEqual<Zero<I>, B>,
+    ) -> Less<K, Z> {
+        unsafe { Less::conjure() }
+    }
+}
+
+impl_all3!(Product);
+
 /// Compare two values for partial equality.
 pub fn partial_equal<'a, X, Y, T>(x: &'a X, y: &'a Y) -> Option<Equal<X, Y>>
     where &'a X: Value<Value=T>,
@@ -257,6 +840,165 @@ pub fn compare<'a, X, Y, T>(x: &'a X, y: &'a Y)
     }
 }
 
+/// A proposition that can be decided at run time.
+///
+/// `A` is whatever the decision procedure needs to inspect, typically
+/// references to the values that the proposition is about.
+pub trait Decidable<A>: Sized {
+    /// Decide whether the proposition holds.
+    fn decide(args: A) -> Result<Self, Not<Self>>;
+}
+
+impl<'a, 'x, 'y, I: Eq> Decidable<(&'a Val<'x, I>, &'a Val<'y, I>)>
+    for Equal<Val<'x, I>, Val<'y, I>> {
+    fn decide((x, y): (&'a Val<'x, I>, &'a Val<'y, I>))
+              -> Result<Self, Not<Self>> {
+        equal(x, y)
+    }
+}
+
+impl<'a, 'x, 'y, I: Ord> Decidable<(&'a Val<'x, I>, &'a Val<'y, I>)>
+    for Less<Val<'x, I>, Val<'y, I>> {
+    fn decide((x, y): (&'a Val<'x, I>, &'a Val<'y, I>))
+              -> Result<Self, Not<Self>> {
+        match compare(x, y) {
+            Ok(lt) => Ok(lt),
+            Err(Ok(gt)) => Err(gt.asym()),
+            Err(Err(eq)) => Err(eq.not_less()),
+        }
+    }
+}
+
+impl<'a, 'x, 'y, I: Ord> Decidable<(&'a Val<'x, I>, &'a Val<'y, I>)>
+    for LessEqual<Val<'x, I>, Val<'y, I>> {
+    fn decide((x, y): (&'a Val<'x, I>, &'a Val<'y, I>))
+              -> Result<Self, Not<Self>> {
+        match compare(x, y) {
+            Ok(lt) => Ok(LessEqual::from(lt)),
+            Err(Err(eq)) => Ok(LessEqual::from(eq)),
+            Err(Ok(gt)) => Err(gt.not_ge()),
+        }
+    }
+}
+
+impl<'a, 'x, I: num::Zero> Decidable<&'a Val<'x, I>>
+    for NonZero<Val<'x, I>> {
+    fn decide(x: &'a Val<'x, I>) -> Result<Self, Not<Self>> {
+        decide_nonzero(x)
+    }
+}
+
+impl<'a, 'x, I: num::Zero + PartialOrd> Decidable<&'a Val<'x, I>>
+    for Positive<Val<'x, I>> {
+    fn decide(x: &'a Val<'x, I>) -> Result<Self, Not<Self>> {
+        decide_positive(x)
+    }
+}
+
+impl<A, P: Decidable<A>> Decidable<A> for Not<P> {
+    fn decide(args: A) -> Result<Self, Not<Self>> {
+        match P::decide(args) {
+            Ok(_) => Err(unsafe { Not::conjure() }),
+            Err(not_p) => Ok(not_p),
+        }
+    }
+}
+
+/// Decide a proposition.
+///
+/// ## Example
+///
+/// ```
+/// use imprint::imprint;
+/// use imprint::arith::{Dec, Less, decide};
+///
+/// imprint(1, |x| { imprint(2, |y| {
+///     let d: Dec<Less<_, _>> = decide((&x, &y));
+///     assert!(d.is_yes());
+/// }) })
+/// ```
+pub fn decide<P: Decidable<A>, A>(args: A) -> Dec<P> {
+    Dec::from(P::decide(args))
+}
+
+/// The outcome of deciding a proposition `P`.
+#[derive(Clone, Copy, Debug)]
+pub enum Dec<P> {
+    Yes(P),
+    No(Not<P>),
+}
+
+impl<P> Dec<P> {
+    pub fn is_yes(&self) -> bool {
+        match *self {
+            Dec::Yes(_) => true,
+            Dec::No(_) => false,
+        }
+    }
+
+    pub fn is_no(&self) -> bool {
+        !self.is_yes()
+    }
+
+    pub fn into_result(self) -> Result<P, Not<P>> {
+        match self {
+            Dec::Yes(p) => Ok(p),
+            Dec::No(not_p) => Err(not_p),
+        }
+    }
+
+    /// Transform the outcome into that of an equivalent proposition.
+    pub fn map<Q, F, G>(self, yes: F, no: G) -> Dec<Q>
+        where F: FnOnce(P) -> Q, G: FnOnce(Not<P>) -> Not<Q> {
+        match self {
+            Dec::Yes(p) => Dec::Yes(yes(p)),
+            Dec::No(not_p) => Dec::No(no(not_p)),
+        }
+    }
+
+    /// `Dec(P) -> Dec(¬P)`
+    pub fn negate(self) -> Dec<Not<P>> {
+        match self {
+            Dec::Yes(_) => Dec::No(unsafe { Not::conjure() }),
+            Dec::No(not_p) => Dec::Yes(not_p),
+        }
+    }
+
+    /// `(Dec(P), Dec(Q)) -> Dec(P ∧ Q)`
+    pub fn and<Q>(self, other: Dec<Q>) -> Dec<And<P, Q>> {
+        match (self, other) {
+            (Dec::Yes(p), Dec::Yes(q)) => Dec::Yes(And(p, q)),
+            _ => Dec::No(unsafe { Not::conjure() }),
+        }
+    }
+
+    /// `(Dec(P), Dec(Q)) -> Dec(P ∨ Q)`
+    pub fn or<Q>(self, other: Dec<Q>) -> Dec<Or<P, Q>> {
+        match (self, other) {
+            (Dec::Yes(p), _) => Dec::Yes(Or::Left(p)),
+            (_, Dec::Yes(q)) => Dec::Yes(Or::Right(q)),
+            (Dec::No(not_p), Dec::No(not_q)) => {
+                Dec::No(And(not_p, not_q).demorgan())
+            }
+        }
+    }
+}
+
+impl<P> From<Result<P, Not<P>>> for Dec<P> {
+    fn from(result: Result<P, Not<P>>) -> Self {
+        match result {
+            Ok(p) => Dec::Yes(p),
+            Err(not_p) => Dec::No(not_p),
+        }
+    }
+}
+
+impl<P> From<Dec<P>> for Result<P, Not<P>> {
+    fn from(dec: Dec<P>) -> Self {
+        dec.into_result()
+    }
+}
+
 pub fn succ<'x, 'y, I, F, R>(x: &Val<'x, I>,
                              _: Less<Val<'x, I>, Val<'y, I>>,
                              callback: F) -> R
@@ -271,6 +1013,172 @@ pub fn succ<'x, 'y, I, F, R>(x: &Val<'x, I>,
     })
 }
 
+/// Imprint the predecessor of `x`.  The evidence that `x` is greater than
+/// some `W` rules out underflow.
+pub fn pred<'x, W: ?Sized, I, F, R>(x: &Val<'x, I>,
+                                    _: Less<W, Val<'x, I>>,
+                                    callback: F) -> R
+    where F: for<'z> FnOnce(Val<'z, I>,
+                            Less<Val<'z, I>, Val<'x, I>>,
+                            LessEqual<W, Val<'z, I>>) -> R,
+          I: Clone + Integer {
+    imprint(x.value().clone() - I::one(), |z| {
+        callback(z,
+                 unsafe { Less::conjure() },
+                 unsafe { LessEqual::conjure() })
+    })
+}
+
+/// Imprint the smaller of two values.
+pub fn min<'x, 'y, I, F, R>(x: &Val<'x, I>, y: &Val<'y, I>, callback: F) -> R
+    where F: for<'z> FnOnce(Val<'z, I>,
+                            LessEqual<Val<'z, I>, Val<'x, I>>,
+                            LessEqual<Val<'z, I>, Val<'y, I>>) -> R,
+          I: Clone + Ord {
+    imprint(cmp::min(x.value(), y.value()).clone(), |z| {
+        callback(z,
+                 unsafe { LessEqual::conjure() },
+                 unsafe { LessEqual::conjure() })
+    })
+}
+
+/// Imprint the larger of two values.
+pub fn max<'x, 'y, I, F, R>(x: &Val<'x, I>, y: &Val<'y, I>, callback: F) -> R
+    where F: for<'z> FnOnce(Val<'z, I>,
+                            LessEqual<Val<'x, I>, Val<'z, I>>,
+                            LessEqual<Val<'y, I>, Val<'z, I>>) -> R,
+          I: Clone + Ord {
+    imprint(cmp::max(x.value(), y.value()).clone(), |z| {
+        callback(z,
+                 unsafe { LessEqual::conjure() },
+                 unsafe { LessEqual::conjure() })
+    })
+}
+
+/// Imprint the value of `x` restricted to the interval `[lo, hi]`.
+pub fn clamp<'x, 'l, 'h, I, F, R>(x: &Val<'x, I>,
+                                  lo: &Val<'l, I>,
+                                  hi: &Val<'h, I>,
+                                  _: LessEqual<Val<'l, I>, Val<'h, I>>,
+                                  callback: F) -> R
+    where F: for<'z> FnOnce(Val<'z, I>,
+                            LessEqual<Val<'l, I>, Val<'z, I>>,
+                            LessEqual<Val<'z, I>, Val<'h, I>>) -> R,
+          I: Clone + Ord {
+    let z = if x.value() < lo.value() {
+        lo.value()
+    } else if x.value() > hi.value() {
+        hi.value()
+    } else {
+        x.value()
+    };
+    imprint(z.clone(), |z| {
+        callback(z,
+                 unsafe { LessEqual::conjure() },
+                 unsafe { LessEqual::conjure() })
+    })
+}
+
+/// Imprint the zero of `I`.  The callback receives evidence that the
+/// imprinted value is equal to `Zero<I>`.
+pub fn zero<I, F, R>(callback: F) -> R
+    where F: for<'z> FnOnce(Val<'z, I>, Equal<Val<'z, I>, Zero<I>>) -> R,
+          I: num::Zero {
+    imprint(I::zero(), |z| callback(z, unsafe { Equal::conjure() }))
+}
+
+/// Decide whether a value is nonzero.
+pub fn decide_nonzero<'x, I>(x: &Val<'x, I>)
+                             -> Result<NonZero<Val<'x, I>>,
+                                       Not<NonZero<Val<'x, I>>>>
+    where I: num::Zero {
+    if x.value().is_zero() {
+        Err(unsafe { Not::conjure() })
+    } else {
+        Ok(unsafe { NonZero::conjure() })
+    }
+}
+
+/// Decide whether a value is greater than zero.
+pub fn decide_positive<'x, I>(x: &Val<'x, I>)
+                              -> Result<Positive<Val<'x, I>>,
+                                        Not<Positive<Val<'x, I>>>>
+    where I: num::Zero + PartialOrd {
+    if x.value() > &I::zero() {
+        Ok(unsafe { Positive::conjure() })
+    } else {
+        Err(unsafe { Not::conjure() })
+    }
+}
+
+/// Add two values, checking for overflow.  If the sum is representable, it
+/// is imprinted and passed to the callback along with evidence of the sum.
+pub fn add<'x, 'y, I, F, R>(x: &Val<'x, I>, y: &Val<'y, I>, callback: F)
+                            -> Option<R>
+    where F: for<'z> FnOnce(Val<'z, I>,
+                            Sum<Val<'x, I>, Val<'y, I>, Val<'z, I>>) -> R,
+          I: CheckedAdd {
+    x.value().checked_add(y.value()).map(|z| {
+        imprint(z, |z| callback(z, unsafe { Sum::conjure() }))
+    })
+}
+
+/// Subtract two unsigned values.  The evidence that `y <= x` rules out
+/// underflow, so the difference can always be imprinted.
+pub fn sub<'x, 'y, I, F, R>(x: &Val<'x, I>,
+                            y: &Val<'y, I>,
+                            _: LessEqual<Val<'y, I>, Val<'x, I>>,
+                            callback: F) -> R
+    where F: for<'z> FnOnce(Val<'z, I>,
+                            Difference<Val<'x, I>, Val<'y, I>, Val<'z, I>>)
+                            -> R,
+          I: Clone + Integer + Unsigned {
+    imprint(x.value().clone() - y.value().clone(), |z| {
+        callback(z, unsafe { Difference::conjure() })
+    })
+}
+
+/// Multiply two integers, checking for overflow.  If the product is
+/// representable, it is imprinted and passed to the callback along with
+/// evidence of the product.
+pub fn mul<'x, 'y, I, F, R>(x: &Val<'x, I>, y: &Val<'y, I>, callback: F)
+                            -> Option<R>
+    where F: for<'z> FnOnce(Val<'z, I>,
+                            Product<Val<'x, I>, Val<'y, I>, Val<'z, I>>)
+                            -> R,
+          I: CheckedMul + Integer {
+    x.value().checked_mul(y.value()).map(|z| {
+        imprint(z, |z| callback(z, unsafe { Product::conjure() }))
+    })
+}
+
+/// Divide two unsigned integers, obtaining both the quotient `q` and the
+/// remainder `r`.  The divisor `y` must be nonzero.
+///
+/// The callback receives evidence that `r < y` and that `q * y + r = x`.
+pub fn div_rem<'x, 'y, I, F, R>(x: &Val<'x, I>,
+                                y: &Val<'y, I>,
+                                _: NonZero<Val<'y, I>>,
+                                callback: F) -> R
+    where F: for<'q, 'r> FnOnce(Val<'q, I>,
+                                Val<'r, I>,
+                                Less<Val<'r, I>, Val<'y, I>>,
+                                Equal<Plus<Times<Val<'q, I>, Val<'y, I>>,
+                                           Val<'r, I>>,
+                                      Val<'x, I>>) -> R,
+          I: Integer + Unsigned {
+    let (q, r) = x.value().div_rem(y.value());
+    imprint(q, |q| imprint(r, |r| {
+        callback(q, r, unsafe { Less::conjure() }, unsafe { Equal::conjure() })
+    }))
+}
+
+/// Unsigned values are never negative.
+pub fn nonneg<'x, I>(_: &Val<'x, I>) -> LessEqual<Zero<I>, Val<'x, I>>
+    where I: Integer + Unsigned {
+    unsafe { LessEqual::conjure() }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::*;
@@ -289,4 +1197,166 @@ mod tests {
             assert!(partial_equal(&one, &one_).is_some());
         }) }) });
     }
+
+    #[test]
+    fn connectives() {
+        imprint(1, |one| { imprint(2, |two| {
+            let lt = compare(&one, &two).unwrap();
+            let ne = equal(&one, &two).unwrap_err();
+            let _: Less<_, _> = And(lt, ne).comm().right();
+            let either = Or::from(equal(&one, &two).map_err(|_| lt));
+            let _: Less<_, _> = either.comm().resolve_right(ne);
+            let weaken = Implies::new(LessEqual::from);
+            let iff = Iff(weaken.clone(), Implies::new(move |_| lt));
+            let _: Less<_, _> = iff.sym().mp(weaken.apply(lt));
+            let nope: Not<Or<Equal<_, _>, Equal<_, _>>> =
+                And(ne, ne).demorgan();
+            let _: Not<Equal<_, _>> = nope.demorgan().left();
+        }) });
+    }
+
+    #[test]
+    fn decidable() {
+        imprint(1, |one| { imprint(2, |two| {
+            assert!(decide::<Less<_, _>, _>((&one, &two)).is_yes());
+            assert!(decide::<Less<_, _>, _>((&two, &one)).is_no());
+            assert!(decide::<LessEqual<_, _>, _>((&one, &one)).is_yes());
+            assert!(decide::<Not<Equal<_, _>>, _>((&one, &two)).is_yes());
+            let both = decide::<Less<_, _>, _>((&one, &two))
+                .and(decide::<NonZero<_>, _>(&one));
+            assert!(both.is_yes());
+            let either = decide::<Equal<_, _>, _>((&one, &two))
+                .or(decide::<Positive<_>, _>(&two));
+            assert!(either.negate().is_no());
+            let swapped: Dec<And<_, _>> =
+                both.map(And::comm, |_| unreachable!());
+            assert!(swapped.into_result().is_ok());
+        }) });
+    }
+
+    #[test]
+    fn order() {
+        imprint(1, |one| { imprint(2, |two| { imprint(1, |one_| {
+            let lt = compare(&one, &two).unwrap();
+            let le = LessEqual::from(lt);
+            assert!(le.split(&one, &two).into_result().is_ok());
+            let eq = LessEqual::from(equal(&one, &one_).unwrap())
+                .split(&one, &one_).into_result().unwrap_err();
+            let _: Equal<_, _> = LessEqual::from(eq)
+                .antisym(LessEqual::from(eq.sym()));
+            let _: Not<Less<_, _>> = le.not_gt();
+            let _: Not<Less<Val<i32>, Val<i32>>> = Less::irrefl();
+            let _: Not<Equal<_, _>> = lt.not_equal();
+            let ngt: Not<Less<_, _>> =
+                Decidable::decide((&two, &one)).unwrap_err();
+            let _: Less<_, _> = ngt.into_ge().lt_of_ne(lt.not_equal());
+            let nge: Not<LessEqual<_, _>> = lt.not_ge();
+            let _: Less<_, _> = nge.into_gt().rcomp_le(LessEqual::refl());
+        }) }) });
+    }
+
+    #[test]
+    fn succ_pred() {
+        imprint(3, |x| { imprint(5, |y| {
+            let lt = compare(&x, &y).unwrap();
+            pred(&y, lt, |z, z_lt_y, x_le_z| {
+                assert_eq!(z.value(), 4);
+                succ(&z, z_lt_y, |w, _, w_le_y| {
+                    assert!(w_le_y.split(&w, &y).into_result().is_err());
+                });
+                assert!(x_le_z.split(&x, &z).into_result().is_ok());
+            })
+        }) });
+    }
+
+    #[test]
+    fn min_max_clamp() {
+        imprint(3, |x| { imprint(5, |y| {
+            min(&x, &y, |m, le_x, _| {
+                assert_eq!(m.value(), 3);
+                let _ = le_x.antisym(decide((&x, &m)).into_result().unwrap());
+            });
+            max(&x, &y, |m, _, _| assert_eq!(m.value(), 5));
+            imprint(4, |lo| {
+                let le = decide((&lo, &y)).into_result().unwrap();
+                clamp(&x, &lo, &y, le, |c, lo_le, _| {
+                    assert_eq!(c.value(), 4);
+                    assert!(lo_le.split(&lo, &c).into_result().is_err());
+                });
+            })
+        }) });
+    }
+
+    #[test]
+    fn add_works() {
+        imprint(2u8, |two| { imprint(3u8, |three| {
+            let pos = compare(&Zero::new(), &three).unwrap();
+            let five = add(&two, &three, |five, sum| {
+                assert!(compare(&two, &five).is_ok());
+                let _ = sum.lt_of_pos(pos);
+                let _ = sum.comm().le_of_nonneg(nonneg(&two));
+                five.value()
+            });
+            assert_eq!(five, Some(5));
+            imprint(255u8, |max| {
+                assert!(add(&max, &two, |_, _| ()).is_none());
+            })
+        }) });
+    }
+
+    #[test]
+    fn sub_works() {
+        imprint(7usize, |seven| { imprint(3usize, |three| {
+            let le = LessEqual::from(compare(&three, &seven).unwrap());
+            sub(&seven, &three, le, |four, diff| {
+                assert_eq!(four.value(), 4);
+                let _: LessEqual<_, _> = diff.le_of_nonneg(nonneg(&three));
+                let _: Sum<_, _, _> = diff.swap().into_sum();
+                assert!(compare(&four, &seven).is_ok());
+            })
+        }) });
+    }
+
+    #[test]
+    fn mul_works() {
+        imprint(6u8, |six| { imprint(7u8, |seven| {
+            let n = mul(&six, &seven, |n, prod| {
+                let pos = compare(&Zero::new(), &seven).unwrap();
+                let _ = prod.le_of_pos(nonneg(&six), pos);
+                assert!(compare(&six, &n).is_ok());
+                n.value()
+            });
+            assert_eq!(n, Some(42));
+            imprint(64u8, |big| {
+                assert!(mul(&big, &six, |_, _| ()).is_none());
+            })
+        }) });
+    }
+
+    #[test]
+    fn nonzero_works() {
+        imprint(0u32, |z| { imprint(3u32, |x| {
+            assert!(decide_nonzero(&z).is_err());
+            assert!(decide_positive(&z).is_err());
+            let nz = decide_nonzero(&x).unwrap();
+            assert!(decide_positive(&x).is_ok());
+            zero(|z: Val<u32>, eq| {
+                assert_eq!(z.value(), 0);
+                let lt = nz.into_positive().into_less().lsubst(eq.sym());
+                assert!(compare(&z, &x).is_ok());
+                let _ = NonZero::from(Positive::from(lt.lsubst(eq)));
+            })
+        }) });
+    }
+
+    #[test]
+    fn div_rem_works() {
+        imprint(17u32, |x| { imprint(5u32, |y| {
+            let nz = NonZero::from(compare(&Zero::new(), &y).unwrap());
+            div_rem(&x, &y, nz, |q, r, _, _| {
+                assert_eq!((q.value(), r.value()), (3, 2));
+                assert!(compare(&r, &y).is_ok());
+            })
+        }) });
+    }
 }
@@ -1,9 +1,12 @@
+use std::{cmp, iter, slice, vec};
+use std::hash::{Hash, Hasher};
+use std::ptr;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut, Index, IndexMut};
 use num::Zero;
 use num_iter::{Range, range};
 use super::*;
-use arith::{self, Equal, Less, LessEqual};
+use arith::{self, Equal, Less, LessEqual, NonZero, Product, Sum};
 
 /// Represents a value less than `'l`.
 ///
@@ -46,6 +49,93 @@ impl<'l> Ix<'l> {
         self.with(|i, self_lt| Ix::new(i, self_lt.rcomp_le(le)))
     }
 
+    /// The index after this one, unless this is the last index.
+    pub fn next(self, len: Val<'l, usize>) -> Option<Self> {
+        self.with(|i, lt| arith::succ(&i, lt, |j, _, le| {
+            le.split(&j, &len).into_result().ok().map(|lt| Ix::new(j, lt))
+        }))
+    }
+
+    /// The index before this one, unless this is the first index.
+    pub fn prev(self) -> Option<Self> {
+        self.with(|i, lt| arith::decide_positive(&i).ok().map(|pos| {
+            arith::pred(&i, pos.into_less(), |j, j_lt, _| {
+                Ix::new(j, j_lt.comp(lt))
+            })
+        }))
+    }
+
+    /// Converts an index of the left part of a split (see `Sl::split_at`)
+    /// into an index of the whole.
+    pub fn lift_left<'n, 'k>(self, _: Sum<Val<'l, usize>,
+                                          Val<'n, usize>,
+                                          Val<'k, usize>>) -> Ix<'k> {
+        // self < 'l <= 'l + 'n
+        unsafe { Ix::from_raw(self.inner) }
+    }
+
+    /// Converts an index of the right part of a split (see `Sl::split_at`)
+    /// into an index of the whole.
+    pub fn lift_right<'m, 'k>(self, offset: Val<'m, usize>,
+                              _: Sum<Val<'m, usize>,
+                                     Val<'l, usize>,
+                                     Val<'k, usize>>) -> Ix<'k> {
+        // 'm + self < 'm + 'l
+        unsafe { Ix::from_raw(offset.value() + self.inner) }
+    }
+
+    /// Determines which part of a split (see `Sl::split_at`) this index
+    /// falls in, converting it into an index of that part.
+    pub fn split<'m, 'n>(self, mid: Val<'m, usize>,
+                         _: Sum<Val<'m, usize>,
+                                Val<'n, usize>,
+                                Val<'l, usize>>)
+                         -> Result<Ix<'m>, Ix<'n>> {
+        if self.inner < mid.value() {
+            Ok(unsafe { Ix::from_raw(self.inner) })
+        } else {
+            Err(unsafe { Ix::from_raw(self.inner - mid.value()) })
+        }
+    }
+
+    /// Flattens a row-major index `(row, col)` of a `'r`-by-`'c` array into
+    /// an index of its underlying storage.
+    pub fn flatten<'r, 'c>(row: Ix<'r>, col: Ix<'c>, cols: Val<'c, usize>,
+                           _: Product<Val<'r, usize>,
+                                      Val<'c, usize>,
+                                      Val<'l, usize>>) -> Self {
+        // row * cols + col < rows * cols (see Product::index_lt)
+        unsafe { Ix::from_raw(*row * cols.value() + *col) }
+    }
+
+    /// The first index of a nonempty range.
+    pub fn first(nonzero: NonZero<Val<'l, usize>>) -> Self {
+        arith::zero(|z, eq| {
+            Ix::new(z, nonzero.into_positive().into_less().lsubst(eq.sym()))
+        })
+    }
+
+    /// The last index of a nonempty range.
+    pub fn last(len: Val<'l, usize>, _: NonZero<Val<'l, usize>>) -> Self {
+        unsafe { Ix::from_raw(len.value() - 1) }
+    }
+
+    /// Clamps `index` to the last index of a nonempty range.
+    pub fn clamp(index: usize, len: Val<'l, usize>,
+                 nonzero: NonZero<Val<'l, usize>>) -> Self {
+        Ix::last(len, nonzero).with(|last, lt| imprint(index, |i| {
+            arith::min(&i, &last, |m, _, le| Ix::new(m, le.rcomp_lt(lt)))
+        }))
+    }
+
+    /// Reduces `index` modulo `len`, which must be nonzero.
+    pub fn wrap(index: usize, len: Val<'l, usize>,
+                nonzero: NonZero<Val<'l, usize>>) -> Self {
+        imprint(index, |i| {
+            arith::div_rem(&i, &len, nonzero, |_, r, lt, _| Ix::new(r, lt))
+        })
+    }
+
     pub unsafe fn from_raw(index: usize) -> Self {
         imprint(index, |i| {
             Ix::new(i, Less::conjure())
@@ -60,7 +150,7 @@ impl<'l> Ix<'l> {
 impl<'i> fmt::Debug for Ix<'i> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.write_str("Ix(")?;
-        (*self).fmt(f)?;
+        self.inner.fmt(f)?;
         f.write_str(")")
     }
 }
@@ -84,7 +174,109 @@ impl<'l> Deref for Ix<'l> {
     }
 }
 
+/// Represents a position at or before `'l`, such as the point at which an
+/// element could be inserted into a slice of length `'l`.
+///
+/// Semantically equivalent to:
+/// `exists<'p> (LessEqual<Val<'p, usize>, Val<'l, usize>>, Val<'p, usize>)`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InsertPos<'l> {
+    len: PhantomData<Val<'l, usize>>,
+    inner: usize,
+}
+
+impl<'l> InsertPos<'l> {
+    pub fn new<'p>(pos: Val<'p, usize>,
+                   _: LessEqual<Val<'p, usize>, Val<'l, usize>>) -> Self {
+        InsertPos { len: PhantomData, inner: pos.value() }
+    }
+
+    /// The position at the end.
+    pub fn end(len: Val<'l, usize>) -> Self {
+        Self::new(len, LessEqual::refl())
+    }
+
+    pub fn with<F, R>(self, callback: F) -> R
+        where F: for<'p> FnOnce(Val<'p, usize>,
+                                LessEqual<Val<'p, usize>,
+                                          Val<'l, usize>>) -> R {
+        callback(unsafe { Val::known(self.inner) },
+                 unsafe { LessEqual::conjure() })
+    }
+
+    /// The index at this position, unless it is at the end.
+    pub fn to_ix(self, len: Val<'l, usize>) -> Option<Ix<'l>> {
+        self.with(|p, le| {
+            le.split(&p, &len).into_result().ok().map(|lt| Ix::new(p, lt))
+        })
+    }
+
+    /// `[0 .. pos)`
+    pub fn range_to(self) -> IxRange<'l> {
+        self.with(|p, le| IxRange::new_with(Zero::zero(), p, le))
+    }
+
+    /// `[pos .. len)`
+    pub fn range_from(self, len: Val<'l, usize>) -> IxRange<'l> {
+        IxRange::new_from(self.inner, len)
+    }
+
+    pub fn into_inner(self) -> usize {
+        self.inner
+    }
+}
+
+impl<'l> From<Ix<'l>> for InsertPos<'l> {
+    fn from(ix: Ix<'l>) -> Self {
+        ix.with(|i, lt| InsertPos::new(i, LessEqual::from(lt)))
+    }
+}
+
+impl<'l> fmt::Debug for InsertPos<'l> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("InsertPos(")?;
+        self.inner.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<'l> Deref for InsertPos<'l> {
+    type Target = usize;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// The natural order of a type, as given by `Ord`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Natural;
+
+/// Lifts a position found by searching a slice of length `'l`.
+///
+/// The search is given an arbitrary comparator, and `std` makes no promise
+/// about its result when the slice isn't sorted by it, so the position is
+/// checked, falling back to the end.
+fn insert_pos<'l>(pos: usize, len: Val<'l, usize>) -> InsertPos<'l> {
+    if pos <= len.value() {
+        InsertPos { len: PhantomData, inner: pos }
+    } else {
+        InsertPos::end(len)
+    }
+}
+
+/// Lifts the result of a search over a slice of length `'l`, checking it
+/// like `insert_pos`.
+fn search_result<'l>(result: Result<usize, usize>, len: Val<'l, usize>)
+                     -> Result<Ix<'l>, InsertPos<'l>> {
+    match result {
+        Ok(i) if i < len.value() => Ok(unsafe { Ix::from_raw(i) }),
+        Ok(_) => Err(InsertPos::end(len)),
+        Err(p) => Err(insert_pos(p, len)),
+    }
+}
+
 /// An owned variant of `Sl`.
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct BoxedSl<'l, T> {
     len: PhantomData<Val<'l, usize>>,
     inner: Box<[T]>,
@@ -125,6 +317,121 @@ impl<'l, T> BoxedSl<'l, T> {
         unsafe { MutSl::from_raw((**self).as_mut_ptr()) }
     }
 
+    pub fn as_sl_len<'a>(&'a self) -> SlLen<'a, 'l, T> {
+        self.as_sl().with_len(self.len())
+    }
+
+    pub fn iter_enumerated<'a>(&'a self) -> IterEnumerated<'a, 'l, T> {
+        self.as_sl().iter_enumerated(self.len())
+    }
+
+    /// See [`Sl::chunks_exact`](struct.Sl.html#method.chunks_exact).
+    pub fn chunks_exact<'a, 'k>(&'a self, k: Val<'k, usize>,
+                                nonzero: NonZero<Val<'k, usize>>)
+                                -> ChunksExact<'a, 'k, T> {
+        self.as_sl().chunks_exact(self.len(), k, nonzero)
+    }
+
+    /// See [`MutSl::chunks_exact_mut`](
+    /// struct.MutSl.html#method.chunks_exact_mut).
+    pub fn chunks_exact_mut<'a, 'k>(&'a mut self, k: Val<'k, usize>,
+                                    nonzero: NonZero<Val<'k, usize>>)
+                                    -> ChunksExactMut<'a, 'k, T> {
+        let len = self.len();
+        self.as_mut_sl().chunks_exact_mut(len, k, nonzero)
+    }
+
+    /// See [`Sl::windows`](struct.Sl.html#method.windows).
+    pub fn windows<'a, 'k>(&'a self, k: Val<'k, usize>,
+                           nonzero: NonZero<Val<'k, usize>>)
+                           -> Windows<'a, 'k, T> {
+        self.as_sl().windows(self.len(), k, nonzero)
+    }
+
+    /// Applies `f` to every element, preserving the brand.
+    pub fn map<U, F>(self, f: F) -> BoxedSl<'l, U>
+        where F: FnMut(T) -> U {
+        let vec: Vec<_> = self.inner.into_vec().into_iter().map(f).collect();
+        unsafe { BoxedSl::from_raw(vec.into_boxed_slice()) }
+    }
+
+    /// See [`zip_with`](fn.zip_with.html).
+    pub fn zip_with<'a, 'b, U, V, F>(&'a self, other: &'b BoxedSl<'l, U>,
+                                     f: F) -> BoxedSl<'l, V>
+        where F: FnMut(&'a T, &'b U) -> V {
+        zip_with(self.as_sl(), other.as_sl(), self.len(), f)
+    }
+
+    /// See [`MutSl::sort`](struct.MutSl.html#method.sort).
+    pub fn sort<'a>(&'a mut self) -> Sorted<'l, 'a, T> where T: Ord {
+        let len = self.len();
+        self.as_mut_sl().sort(len)
+    }
+
+    /// See [`MutSl::sort`](struct.MutSl.html#method.sort).
+    pub fn sort_by_key<'a, B, F>(&'a mut self, f: F) -> Sorted<'l, 'a, T, F>
+        where B: Ord, F: Fn(&T) -> B {
+        let len = self.len();
+        self.as_mut_sl().sort_by_key(len, f)
+    }
+
+    /// See [`SlLen::binary_search`](struct.SlLen.html#method.binary_search).
+    pub fn binary_search(&self, x: &T) -> Result<Ix<'l>, InsertPos<'l>>
+        where T: Ord {
+        self.as_sl_len().binary_search(x)
+    }
+
+    /// See [`SlLen::binary_search`](struct.SlLen.html#method.binary_search).
+    pub fn binary_search_by<'a, F>(&'a self, f: F)
+                                   -> Result<Ix<'l>, InsertPos<'l>>
+        where F: FnMut(&'a T) -> cmp::Ordering {
+        self.as_sl_len().binary_search_by(f)
+    }
+
+    /// See [`SlLen::binary_search`](struct.SlLen.html#method.binary_search).
+    pub fn binary_search_by_key<'a, B, F>(&'a self, b: &B, f: F)
+                                          -> Result<Ix<'l>, InsertPos<'l>>
+        where B: Ord, F: FnMut(&'a T) -> B {
+        self.as_sl_len().binary_search_by_key(b, f)
+    }
+
+    /// See [`SlLen::partition_point`](
+    /// struct.SlLen.html#method.partition_point).
+    pub fn partition_point<P>(&self, pred: P) -> InsertPos<'l>
+        where P: FnMut(&T) -> bool {
+        self.as_sl_len().partition_point(pred)
+    }
+
+    pub fn iter_enumerated_mut<'a>(&'a mut self)
+                                   -> IterEnumeratedMut<'a, 'l, T> {
+        IterEnumeratedMut {
+            len: PhantomData,
+            inner: self.iter_mut().enumerate(),
+        }
+    }
+
+    /// See [`Sl::split_at`](struct.Sl.html#method.split_at).
+    pub fn split_at<'a, F, R>(&'a self, mid: Ix<'l>, callback: F) -> R
+        where F: for<'m, 'n> FnOnce(Val<'m, usize>, Sl<'a, 'm, T>,
+                                    Val<'n, usize>, Sl<'a, 'n, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'l, usize>>) -> R {
+        self.as_sl().split_at(self.len(), mid, callback)
+    }
+
+    /// See [`MutSl::split_at_mut`](struct.MutSl.html#method.split_at_mut).
+    pub fn split_at_mut<'a, F, R>(&'a mut self, mid: Ix<'l>, callback: F)
+                                  -> R
+        where F: for<'m, 'n> FnOnce(Val<'m, usize>, MutSl<'a, 'm, T>,
+                                    Val<'n, usize>, MutSl<'a, 'n, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'l, usize>>) -> R {
+        let len = self.len();
+        self.as_mut_sl().split_at_mut(len, mid, callback)
+    }
+
     pub fn into_inner(self) -> Box<[T]> {
         self.inner
     }
@@ -151,6 +458,30 @@ impl<'a, 'l, T: fmt::Debug> fmt::Debug for BoxedSl<'l, T> {
     }
 }
 
+impl<'l, T> IntoIterator for BoxedSl<'l, T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_vec().into_iter()
+    }
+}
+
+impl<'a, 'l, T> IntoIterator for &'a BoxedSl<'l, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, 'l, T> IntoIterator for &'a mut BoxedSl<'l, T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<'l, T> Index<Ix<'l>> for BoxedSl<'l, T> {
     type Output = T;
     fn index(&self, index: Ix<'l>) -> &Self::Output {
@@ -164,6 +495,161 @@ impl<'l, T> IndexMut<Ix<'l>> for BoxedSl<'l, T> {
     }
 }
 
+/// A growable variant of `BoxedSl`.
+///
+/// Since the length is part of the type, operations that change the length
+/// consume the vector and produce a new one with a different brand, along
+/// with evidence that relates the old length to the new one.  In particular,
+/// `Ix::convert` can be used to carry indices over after a `push`.
+pub struct BrandedVec<'l, T> {
+    len: PhantomData<Val<'l, usize>>,
+    inner: Vec<T>,
+}
+
+impl<T> BrandedVec<'static, T> {
+    /// Creates an empty vector, whose length is the default `Val`.
+    pub fn new() -> Self {
+        BrandedVec { len: PhantomData, inner: Vec::new() }
+    }
+}
+
+impl<T> Default for BrandedVec<'static, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'l, T> BrandedVec<'l, T> {
+    pub fn from_vec(vec: Vec<T>, len: Val<'l, usize>)
+                    -> Result<Self, Vec<T>> {
+        if vec.len() == len.value() {
+            Ok(unsafe { Self::from_raw(vec, len) })
+        } else {
+            Err(vec)
+        }
+    }
+
+    pub fn from_boxed_sl(boxed_sl: BoxedSl<'l, T>) -> Self {
+        let len = boxed_sl.len();
+        unsafe { Self::from_raw(boxed_sl.into_inner().into_vec(), len) }
+    }
+
+    /// The caller must ensure that `vec.len() == len`.
+    unsafe fn from_raw(vec: Vec<T>, _: Val<'l, usize>) -> Self {
+        BrandedVec { len: PhantomData, inner: vec }
+    }
+
+    pub fn len(&self) -> Val<'l, usize> {
+        unsafe { Val::known(self.inner.len()) }
+    }
+
+    pub fn as_sl<'a>(&'a self) -> Sl<'a, 'l, T> {
+        unsafe { Sl::from_raw(self.inner.as_ptr()) }
+    }
+
+    pub fn as_mut_sl<'a>(&'a mut self) -> MutSl<'a, 'l, T> {
+        unsafe { MutSl::from_raw(self.inner.as_mut_ptr()) }
+    }
+
+    pub fn as_sl_len<'a>(&'a self) -> SlLen<'a, 'l, T> {
+        self.as_sl().with_len(self.len())
+    }
+
+    /// Appends an element, yielding a longer vector.
+    ///
+    /// The index of the new element is `Ix::new(old_len, lt)`.
+    pub fn push<F, R>(self, value: T, callback: F) -> R
+        where F: for<'m> FnOnce(BrandedVec<'m, T>,
+                                Less<Val<'l, usize>, Val<'m, usize>>) -> R {
+        let mut inner = self.inner;
+        inner.push(value);
+        imprint(inner.len(), |len| {
+            callback(unsafe { BrandedVec::from_raw(inner, len) },
+                     unsafe { Less::conjure() })
+        })
+    }
+
+    /// Removes the last element of a nonempty vector, yielding a shorter
+    /// vector.
+    pub fn pop<F, R>(self, _: NonZero<Val<'l, usize>>, callback: F) -> R
+        where F: for<'m> FnOnce(BrandedVec<'m, T>,
+                                T,
+                                Less<Val<'m, usize>, Val<'l, usize>>) -> R {
+        let mut inner = self.inner;
+        let value = inner.pop().expect("NonZero was violated");
+        imprint(inner.len(), |len| {
+            callback(unsafe { BrandedVec::from_raw(inner, len) },
+                     value,
+                     unsafe { Less::conjure() })
+        })
+    }
+
+    /// Shortens the vector to `len`.
+    pub fn truncate<'m>(self, len: Val<'m, usize>,
+                        _: LessEqual<Val<'m, usize>, Val<'l, usize>>)
+                        -> BrandedVec<'m, T> {
+        let mut inner = self.inner;
+        inner.truncate(len.value());
+        unsafe { BrandedVec::from_raw(inner, len) }
+    }
+
+    pub fn into_boxed_sl(self) -> BoxedSl<'l, T> {
+        unsafe { BoxedSl::from_raw(self.inner.into_boxed_slice()) }
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.inner
+    }
+}
+
+impl<'l, T: Clone> Clone for BrandedVec<'l, T> {
+    fn clone(&self) -> Self {
+        BrandedVec { len: PhantomData, inner: self.inner.clone() }
+    }
+}
+
+impl<'l, T> Deref for BrandedVec<'l, T> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'l, T> DerefMut for BrandedVec<'l, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<'l, T: fmt::Debug> fmt::Debug for BrandedVec<'l, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("BrandedVec(")?;
+        self.inner.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<'l, T> IntoIterator for BrandedVec<'l, T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
+
+impl<'l, T> Index<Ix<'l>> for BrandedVec<'l, T> {
+    type Output = T;
+    fn index(&self, index: Ix<'l>) -> &Self::Output {
+        self.as_sl().get(index)
+    }
+}
+
+impl<'l, T> IndexMut<Ix<'l>> for BrandedVec<'l, T> {
+    fn index_mut<'a>(&'a mut self, index: Ix<'l>) -> &'a mut Self::Output {
+        self.as_mut_sl().get_mut(index)
+    }
+}
+
 /// An immutable slice.
 pub struct Sl<'a, 'l, T: 'a> {
     len: PhantomData<(Val<'l, usize>, &'a T)>,
@@ -203,6 +689,63 @@ impl<'a, 'l, T> Sl<'a, 'l, T> {
         use std::slice;
         unsafe { slice::from_raw_parts(self.into_ptr(), len.value()) }
     }
+
+    /// Attach the length to obtain a fat slice.
+    pub fn with_len(self, len: Val<'l, usize>) -> SlLen<'a, 'l, T> {
+        SlLen { sl: self, len }
+    }
+
+    pub fn iter(self, len: Val<'l, usize>) -> slice::Iter<'a, T> {
+        self.into_slice(len).iter()
+    }
+
+    /// Iterate over the elements along with their indices.
+    pub fn iter_enumerated(self, len: Val<'l, usize>)
+                           -> IterEnumerated<'a, 'l, T> {
+        IterEnumerated { len: PhantomData, inner: self.iter(len).enumerate() }
+    }
+
+    /// Iterate over non-overlapping chunks of length `'k`, starting from
+    /// the beginning.
+    pub fn chunks_exact<'k>(self, len: Val<'l, usize>, k: Val<'k, usize>,
+                            _: NonZero<Val<'k, usize>>)
+                            -> ChunksExact<'a, 'k, T> {
+        ChunksExact {
+            len: PhantomData,
+            inner: self.into_slice(len).chunks_exact(k.value()),
+        }
+    }
+
+    /// Iterate over all contiguous windows of length `'k`.
+    pub fn windows<'k>(self, len: Val<'l, usize>, k: Val<'k, usize>,
+                       _: NonZero<Val<'k, usize>>) -> Windows<'a, 'k, T> {
+        Windows {
+            len: PhantomData,
+            inner: self.into_slice(len).windows(k.value()),
+        }
+    }
+
+    /// Splits the slice into `[0 .. mid)` and `[mid .. len)`.
+    ///
+    /// The lengths of the two parts are imprinted as `'m` and `'n`, and the
+    /// callback receives evidence that `'m + 'n = 'l`.  Use `Ix::lift_left`,
+    /// `Ix::lift_right`, and `Ix::split` to convert indices between the
+    /// parts and the whole.
+    pub fn split_at<F, R>(self, len: Val<'l, usize>, mid: Ix<'l>,
+                          callback: F) -> R
+        where F: for<'m, 'n> FnOnce(Val<'m, usize>, Sl<'a, 'm, T>,
+                                    Val<'n, usize>, Sl<'a, 'n, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'l, usize>>) -> R {
+        mid.with(|m, lt| {
+            arith::sub(&len, &m, LessEqual::from(lt), |n, diff| unsafe {
+                let right = Sl::from_raw(self.ptr.add(m.value()));
+                callback(m, Sl::from_raw(self.ptr), n, right,
+                         diff.into_sum().comm())
+            })
+        })
+    }
 }
 
 impl<'a, 'l, T> fmt::Debug for Sl<'a, 'l, T> {
@@ -217,159 +760,2126 @@ impl<'a, 'l, T> fmt::Debug for Sl<'a, 'l, T> {
 impl<'a, 'l, T> Index<Ix<'l>> for Sl<'a, 'l, T> {
     type Output = T;
     fn index(&self, index: Ix<'l>) -> &Self::Output {
-        self.get(index)
+        (*self).get(index)
     }
 }
 
-/// A mutable variant of `Sl`.
-pub struct MutSl<'a, 'l, T: 'a> {
-    len: PhantomData<(Val<'l, usize>, &'a mut T)>,
-    ptr: *mut T,
+/// An immutable slice that carries its own length.
+///
+/// Unlike `Sl`, this is a fat pointer, so it can dereference to `[T]`
+/// without having the length passed back in.
+pub struct SlLen<'a, 'l, T: 'a> {
+    sl: Sl<'a, 'l, T>,
+    len: Val<'l, usize>,
 }
 
-impl<'a, 'l, T> MutSl<'a, 'l, T> {
-    pub fn from_slice(slice: &'a mut [T], len: Val<'l, usize>)
-                      -> Option<Self> {
-        if slice.len() == len.value() {
-            Some(unsafe { Self::from_raw(slice.as_mut_ptr()) })
-        } else {
-            None
-        }
+impl<'a, 'l, T> Clone for SlLen<'a, 'l, T> {
+    fn clone(&self) -> Self {
+        *self
     }
+}
 
-    pub fn as_sl<'b>(&'b self) -> Sl<'b, 'l, T> {
-        unsafe { Sl::from_raw(self.ptr) }
+impl<'a, 'l, T> Copy for SlLen<'a, 'l, T> {}
+
+impl<'a, 'l, T> SlLen<'a, 'l, T> {
+    pub fn from_slice(slice: &'a [T], len: Val<'l, usize>) -> Option<Self> {
+        Sl::from_slice(slice, len).map(|sl| sl.with_len(len))
     }
 
-    pub fn as_slice(&self, len: Val<'l, usize>) -> &[T] {
-        use std::slice;
-        unsafe { slice::from_raw_parts_mut(self.ptr, len.value()) }
+    pub fn len(&self) -> Val<'l, usize> {
+        self.len
     }
 
-    pub fn into_mut_slice(self, len: Val<'l, usize>) -> &'a mut [T] {
-        use std::slice;
-        unsafe { slice::from_raw_parts_mut(self.ptr, len.value()) }
+    /// Drop the length to obtain a thin slice.
+    pub fn as_sl(&self) -> Sl<'a, 'l, T> {
+        self.sl
     }
 
-    pub unsafe fn from_raw(ptr: *mut T) -> Self {
-        MutSl { len: PhantomData, ptr: ptr }
+    pub fn into_slice(self) -> &'a [T] {
+        self.sl.into_slice(self.len)
     }
 
-    fn get_mut(&mut self, index: Ix<'l>) -> &'a mut T {
-        unsafe { &mut *self.ptr.offset(*index as isize) }
+    /// Searches a sorted slice for `x`.  If the slice is not sorted, the
+    /// result is unspecified, but it is checked to be within bounds.
+    pub fn binary_search(self, x: &T) -> Result<Ix<'l>, InsertPos<'l>>
+        where T: Ord {
+        search_result(self.into_slice().binary_search(x), self.len)
     }
 
-    pub fn into_ptr(self) -> *const T {
-        self.ptr
+    /// See [`binary_search`](#method.binary_search).
+    pub fn binary_search_by<F>(self, f: F) -> Result<Ix<'l>, InsertPos<'l>>
+        where F: FnMut(&'a T) -> cmp::Ordering {
+        search_result(self.into_slice().binary_search_by(f), self.len)
     }
 
-    pub fn into_mut_ptr(self) -> *mut T {
-        self.ptr
+    /// See [`binary_search`](#method.binary_search).
+    pub fn binary_search_by_key<B, F>(self, b: &B, f: F)
+                                      -> Result<Ix<'l>, InsertPos<'l>>
+        where B: Ord, F: FnMut(&'a T) -> B {
+        let result = self.into_slice().binary_search_by_key(b, f);
+        search_result(result, self.len)
     }
-}
 
-impl<'a, 'l, T> fmt::Debug for MutSl<'a, 'l, T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str("MutSl(")?;
-        // we can't print the contents without knowledge of its size :/
+    /// Returns the first position at which `pred` is false, assuming that
+    /// the slice is partitioned by `pred`.  If it is not, the result is
+    /// unspecified, but it is checked to be within bounds.
+    pub fn partition_point<P>(self, pred: P) -> InsertPos<'l>
+        where P: FnMut(&T) -> bool {
+        insert_pos(self.into_slice().partition_point(pred), self.len)
+    }
+
+    /// Checks whether the slice is sorted.  If it is, the slice is returned
+    /// along with evidence of its sortedness.
+    pub fn check_sorted(self) -> Option<Sorted<'l, 'a, T>> where T: Ord {
+        let slice = self.into_slice();
+        if slice.windows(2).all(|w| w[0] <= w[1]) {
+            Some(Sorted { sl: self, key: Natural })
+        } else {
+            None
+        }
+    }
+
+    /// See [`check_sorted`](#method.check_sorted).
+    pub fn check_sorted_by_key<B, F>(self, f: F)
+                                     -> Option<Sorted<'l, 'a, T, F>>
+        where B: Ord, F: Fn(&T) -> B {
+        let slice = self.into_slice();
+        if slice.windows(2).all(|w| f(&w[0]) <= f(&w[1])) {
+            Some(Sorted { sl: self, key: f })
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the elements along with their indices.
+    pub fn iter_enumerated(self) -> IterEnumerated<'a, 'l, T> {
+        self.sl.iter_enumerated(self.len)
+    }
+
+    /// See [`Sl::chunks_exact`](struct.Sl.html#method.chunks_exact).
+    pub fn chunks_exact<'k>(self, k: Val<'k, usize>,
+                            nonzero: NonZero<Val<'k, usize>>)
+                            -> ChunksExact<'a, 'k, T> {
+        self.sl.chunks_exact(self.len, k, nonzero)
+    }
+
+    /// See [`Sl::windows`](struct.Sl.html#method.windows).
+    pub fn windows<'k>(self, k: Val<'k, usize>,
+                       nonzero: NonZero<Val<'k, usize>>)
+                       -> Windows<'a, 'k, T> {
+        self.sl.windows(self.len, k, nonzero)
+    }
+}
+
+impl<'a, 'l, T> From<SlLen<'a, 'l, T>> for Sl<'a, 'l, T> {
+    fn from(sl: SlLen<'a, 'l, T>) -> Self {
+        sl.sl
+    }
+}
+
+impl<'a, 'l, T> Deref for SlLen<'a, 'l, T> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        self.into_slice()
+    }
+}
+
+impl<'a, 'l, T: fmt::Debug> fmt::Debug for SlLen<'a, 'l, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("SlLen(")?;
+        (**self).fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'b, 'l, T: PartialEq> PartialEq<SlLen<'b, 'l, T>>
+    for SlLen<'a, 'l, T> {
+    fn eq(&self, other: &SlLen<'b, 'l, T>) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a, 'l, T: Eq> Eq for SlLen<'a, 'l, T> {}
+
+impl<'a, 'l, T: Hash> Hash for SlLen<'a, 'l, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<'a, 'l, T> IntoIterator for SlLen<'a, 'l, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_slice().iter()
+    }
+}
+
+impl<'a, 'b, 'l, T> IntoIterator for &'b SlLen<'a, 'l, T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_slice().iter()
+    }
+}
+
+impl<'a, 'l, T> Index<Ix<'l>> for SlLen<'a, 'l, T> {
+    type Output = T;
+    fn index(&self, index: Ix<'l>) -> &Self::Output {
+        self.sl.get(index)
+    }
+}
+
+/// Evidence that a slice of length `'l`, borrowed for `'a`, is sorted by
+/// the key function `F`.
+///
+/// Rather than a bare proof token, this bundles the evidence with the slice
+/// it was obtained for.  A token that only mentions `'l` and `'a` could be
+/// used to search another slice of the same length borrowed for as long,
+/// whereas this can't.  Mutating the slice requires giving this up.
+pub struct Sorted<'l, 'a, T: 'a, F = Natural> {
+    sl: SlLen<'a, 'l, T>,
+    key: F,
+}
+
+impl<'a, 'l, T, F> Sorted<'l, 'a, T, F> {
+    pub fn len(&self) -> Val<'l, usize> {
+        self.sl.len()
+    }
+
+    pub fn as_sl_len(&self) -> SlLen<'a, 'l, T> {
+        self.sl
+    }
+
+    pub fn into_slice(self) -> &'a [T] {
+        self.sl.into_slice()
+    }
+
+    /// The key function by which the slice is sorted.
+    pub fn key(&self) -> &F {
+        &self.key
+    }
+
+    /// Like [`SlLen::binary_search_by_key`][bsk], but the slice is known to
+    /// be sorted by the key.
+    ///
+    /// [bsk]: struct.SlLen.html#method.binary_search_by_key
+    pub fn search_by_key<B>(&self, b: &B) -> Result<Ix<'l>, InsertPos<'l>>
+        where B: Ord, F: Fn(&T) -> B {
+        self.sl.binary_search_by_key(b, |x| (self.key)(x))
+    }
+}
+
+impl<'a, 'l, T: Ord> Sorted<'l, 'a, T> {
+    /// Like [`SlLen::binary_search`][bs], but the slice is known to be
+    /// sorted.
+    ///
+    /// [bs]: struct.SlLen.html#method.binary_search
+    pub fn search(&self, x: &T) -> Result<Ix<'l>, InsertPos<'l>> {
+        self.sl.binary_search(x)
+    }
+}
+
+impl<'a, 'l, T, F: Clone> Clone for Sorted<'l, 'a, T, F> {
+    fn clone(&self) -> Self {
+        Sorted { sl: self.sl, key: self.key.clone() }
+    }
+}
+
+impl<'a, 'l, T, F: Copy> Copy for Sorted<'l, 'a, T, F> {}
+
+impl<'a, 'l, T, F> Deref for Sorted<'l, 'a, T, F> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        self.sl.into_slice()
+    }
+}
+
+impl<'a, 'l, T: fmt::Debug, F> fmt::Debug for Sorted<'l, 'a, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Sorted(")?;
+        (**self).fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'l, T, F> Index<Ix<'l>> for Sorted<'l, 'a, T, F> {
+    type Output = T;
+    fn index(&self, index: Ix<'l>) -> &Self::Output {
+        &self.sl[index]
+    }
+}
+
+/// A mutable variant of `Sl`.
+pub struct MutSl<'a, 'l, T: 'a> {
+    len: PhantomData<(Val<'l, usize>, &'a mut T)>,
+    ptr: *mut T,
+}
+
+impl<'a, 'l, T> MutSl<'a, 'l, T> {
+    pub fn from_slice(slice: &'a mut [T], len: Val<'l, usize>)
+                      -> Option<Self> {
+        if slice.len() == len.value() {
+            Some(unsafe { Self::from_raw(slice.as_mut_ptr()) })
+        } else {
+            None
+        }
+    }
+
+    pub fn as_sl<'b>(&'b self) -> Sl<'b, 'l, T> {
+        unsafe { Sl::from_raw(self.ptr) }
+    }
+
+    pub fn as_slice(&self, len: Val<'l, usize>) -> &[T] {
+        use std::slice;
+        unsafe { slice::from_raw_parts_mut(self.ptr, len.value()) }
+    }
+
+    pub fn into_mut_slice(self, len: Val<'l, usize>) -> &'a mut [T] {
+        use std::slice;
+        unsafe { slice::from_raw_parts_mut(self.ptr, len.value()) }
+    }
+
+    pub fn iter<'b>(&'b self, len: Val<'l, usize>) -> slice::Iter<'b, T> {
+        self.as_slice(len).iter()
+    }
+
+    pub fn iter_mut<'b>(&'b mut self, len: Val<'l, usize>)
+                        -> slice::IterMut<'b, T> {
+        unsafe { slice::from_raw_parts_mut(self.ptr, len.value()) }.iter_mut()
+    }
+
+    /// Iterate over the elements along with their indices.
+    pub fn iter_enumerated<'b>(&'b self, len: Val<'l, usize>)
+                               -> IterEnumerated<'b, 'l, T> {
+        self.as_sl().iter_enumerated(len)
+    }
+
+    /// Iterate mutably over the elements along with their indices.
+    pub fn iter_enumerated_mut<'b>(&'b mut self, len: Val<'l, usize>)
+                                   -> IterEnumeratedMut<'b, 'l, T> {
+        IterEnumeratedMut {
+            len: PhantomData,
+            inner: self.iter_mut(len).enumerate(),
+        }
+    }
+
+    pub unsafe fn from_raw(ptr: *mut T) -> Self {
+        MutSl { len: PhantomData, ptr: ptr }
+    }
+
+    fn get_mut(&mut self, index: Ix<'l>) -> &'a mut T {
+        unsafe { &mut *self.ptr.offset(*index as isize) }
+    }
+
+    pub fn swap(&mut self, a: Ix<'l>, b: Ix<'l>) {
+        unsafe { ptr::swap(self.ptr.add(*a), self.ptr.add(*b)) }
+    }
+
+    /// A mutable variant of
+    /// [`Sl::chunks_exact`](struct.Sl.html#method.chunks_exact).
+    pub fn chunks_exact_mut<'k>(self, len: Val<'l, usize>, k: Val<'k, usize>,
+                                _: NonZero<Val<'k, usize>>)
+                                -> ChunksExactMut<'a, 'k, T> {
+        ChunksExactMut {
+            len: PhantomData,
+            inner: self.into_mut_slice(len).chunks_exact_mut(k.value()),
+        }
+    }
+
+    /// Sorts the slice, giving up mutable access in exchange for a shared
+    /// slice that is known to be sorted.
+    pub fn sort(self, len: Val<'l, usize>) -> Sorted<'l, 'a, T>
+        where T: Ord {
+        let slice = self.into_mut_slice(len);
+        slice.sort();
+        let sl = unsafe { Sl::from_raw(slice.as_ptr()) }.with_len(len);
+        Sorted { sl, key: Natural }
+    }
+
+    /// See [`sort`](#method.sort).
+    pub fn sort_by_key<B, F>(self, len: Val<'l, usize>, f: F)
+                             -> Sorted<'l, 'a, T, F>
+        where B: Ord, F: Fn(&T) -> B {
+        let slice = self.into_mut_slice(len);
+        slice.sort_by_key(&f);
+        let sl = unsafe { Sl::from_raw(slice.as_ptr()) }.with_len(len);
+        Sorted { sl, key: f }
+    }
+
+    pub fn into_ptr(self) -> *const T {
+        self.ptr
+    }
+
+    pub fn into_mut_ptr(self) -> *mut T {
+        self.ptr
+    }
+
+    /// A mutable variant of [`Sl::split_at`](struct.Sl.html#method.split_at).
+    pub fn split_at_mut<F, R>(self, len: Val<'l, usize>, mid: Ix<'l>,
+                              callback: F) -> R
+        where F: for<'m, 'n> FnOnce(Val<'m, usize>, MutSl<'a, 'm, T>,
+                                    Val<'n, usize>, MutSl<'a, 'n, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'l, usize>>) -> R {
+        mid.with(|m, lt| {
+            arith::sub(&len, &m, LessEqual::from(lt), |n, diff| unsafe {
+                let right = MutSl::from_raw(self.ptr.add(m.value()));
+                callback(m, MutSl::from_raw(self.ptr), n, right,
+                         diff.into_sum().comm())
+            })
+        })
+    }
+}
+
+impl<'a, 'l, T> fmt::Debug for MutSl<'a, 'l, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("MutSl(")?;
+        // we can't print the contents without knowledge of its size :/
         self.ptr.fmt(f)?;
         f.write_str(")")
     }
 }
 
-impl<'a, 'l, T> Index<Ix<'l>> for MutSl<'a, 'l, T> {
-    type Output = T;
-    fn index(&self, index: Ix<'l>) -> &Self::Output {
-        self.as_sl().get(index)
+impl<'a, 'l, T> Index<Ix<'l>> for MutSl<'a, 'l, T> {
+    type Output = T;
+    fn index(&self, index: Ix<'l>) -> &Self::Output {
+        self.as_sl().get(index)
+    }
+}
+
+impl<'a, 'l, T> IndexMut<Ix<'l>> for MutSl<'a, 'l, T> {
+    fn index_mut(&mut self, index: Ix<'l>) -> &mut Self::Output {
+        self.get_mut(index)
+    }
+}
+
+/// An iterator over the elements of a slice along with their indices.
+pub struct IterEnumerated<'a, 'l, T: 'a> {
+    len: PhantomData<Val<'l, usize>>,
+    inner: iter::Enumerate<slice::Iter<'a, T>>,
+}
+
+impl<'a, 'l, T> Clone for IterEnumerated<'a, 'l, T> {
+    fn clone(&self) -> Self {
+        IterEnumerated { len: PhantomData, inner: self.inner.clone() }
+    }
+}
+
+impl<'a, 'l, T> Iterator for IterEnumerated<'a, 'l, T> {
+    type Item = (Ix<'l>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, x)| (unsafe { Ix::from_raw(i) }, x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'l, T> DoubleEndedIterator for IterEnumerated<'a, 'l, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(i, x)| (unsafe { Ix::from_raw(i) }, x))
+    }
+}
+
+impl<'a, 'l, T> ExactSizeIterator for IterEnumerated<'a, 'l, T> {}
+
+/// A mutable variant of `IterEnumerated`.
+pub struct IterEnumeratedMut<'a, 'l, T: 'a> {
+    len: PhantomData<Val<'l, usize>>,
+    inner: iter::Enumerate<slice::IterMut<'a, T>>,
+}
+
+impl<'a, 'l, T> Iterator for IterEnumeratedMut<'a, 'l, T> {
+    type Item = (Ix<'l>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, x)| (unsafe { Ix::from_raw(i) }, x))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'l, T> DoubleEndedIterator for IterEnumeratedMut<'a, 'l, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(i, x)| (unsafe { Ix::from_raw(i) }, x))
+    }
+}
+
+impl<'a, 'l, T> ExactSizeIterator for IterEnumeratedMut<'a, 'l, T> {}
+
+/// An iterator over non-overlapping chunks of length `'k`.
+///
+/// Every chunk shares the brand `'k`, so an `Ix<'k>` is valid for all of
+/// them.  Any leftover elements can be obtained with `with_remainder`.
+pub struct ChunksExact<'a, 'k, T: 'a> {
+    len: PhantomData<Val<'k, usize>>,
+    inner: slice::ChunksExact<'a, T>,
+}
+
+impl<'a, 'k, T> Clone for ChunksExact<'a, 'k, T> {
+    fn clone(&self) -> Self {
+        ChunksExact { len: PhantomData, inner: self.inner.clone() }
+    }
+}
+
+impl<'a, 'k, T> ChunksExact<'a, 'k, T> {
+    /// Calls the callback with the elements that don't fit into a chunk,
+    /// whose length is imprinted as `'r`.
+    pub fn with_remainder<F, R>(&self, callback: F) -> R
+        where F: for<'r> FnOnce(Val<'r, usize>, Sl<'a, 'r, T>) -> R {
+        let remainder = self.inner.remainder();
+        imprint(remainder.len(), |r| {
+            callback(r, unsafe { Sl::from_raw(remainder.as_ptr()) })
+        })
+    }
+}
+
+impl<'a, 'k, T> Iterator for ChunksExact<'a, 'k, T> {
+    type Item = Sl<'a, 'k, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|c| unsafe { Sl::from_raw(c.as_ptr()) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'k, T> DoubleEndedIterator for ChunksExact<'a, 'k, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|c| unsafe { Sl::from_raw(c.as_ptr()) })
+    }
+}
+
+impl<'a, 'k, T> ExactSizeIterator for ChunksExact<'a, 'k, T> {}
+
+/// A mutable variant of `ChunksExact`.
+pub struct ChunksExactMut<'a, 'k, T: 'a> {
+    len: PhantomData<Val<'k, usize>>,
+    inner: slice::ChunksExactMut<'a, T>,
+}
+
+impl<'a, 'k, T> ChunksExactMut<'a, 'k, T> {
+    /// See [`ChunksExact::with_remainder`](
+    /// struct.ChunksExact.html#method.with_remainder).
+    pub fn into_remainder<F, R>(self, callback: F) -> R
+        where F: for<'r> FnOnce(Val<'r, usize>, MutSl<'a, 'r, T>) -> R {
+        let remainder = self.inner.into_remainder();
+        imprint(remainder.len(), |r| {
+            callback(r, unsafe { MutSl::from_raw(remainder.as_mut_ptr()) })
+        })
+    }
+}
+
+impl<'a, 'k, T> Iterator for ChunksExactMut<'a, 'k, T> {
+    type Item = MutSl<'a, 'k, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+            .map(|c| unsafe { MutSl::from_raw(c.as_mut_ptr()) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'k, T> DoubleEndedIterator for ChunksExactMut<'a, 'k, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+            .map(|c| unsafe { MutSl::from_raw(c.as_mut_ptr()) })
+    }
+}
+
+impl<'a, 'k, T> ExactSizeIterator for ChunksExactMut<'a, 'k, T> {}
+
+/// An iterator over overlapping windows of length `'k`.
+pub struct Windows<'a, 'k, T: 'a> {
+    len: PhantomData<Val<'k, usize>>,
+    inner: slice::Windows<'a, T>,
+}
+
+impl<'a, 'k, T> Clone for Windows<'a, 'k, T> {
+    fn clone(&self) -> Self {
+        Windows { len: PhantomData, inner: self.inner.clone() }
+    }
+}
+
+impl<'a, 'k, T> Iterator for Windows<'a, 'k, T> {
+    type Item = Sl<'a, 'k, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|w| unsafe { Sl::from_raw(w.as_ptr()) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'k, T> DoubleEndedIterator for Windows<'a, 'k, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|w| unsafe { Sl::from_raw(w.as_ptr()) })
+    }
+}
+
+impl<'a, 'k, T> ExactSizeIterator for Windows<'a, 'k, T> {}
+
+#[derive(Clone)]
+pub struct IxRange<'l> {
+    len: PhantomData<Val<'l, usize>>,
+    inner: Range<usize>,
+}
+
+impl<'l> IxRange<'l> {
+    pub fn new_with<'i>(start: usize, stop: Val<'i, usize>,
+                        _: LessEqual<Val<'i, usize>, Val<'l, usize>>) -> Self {
+        IxRange {
+            len: PhantomData,
+            inner: range(start, stop.value()),
+        }
+    }
+
+    /// `[start .. stop)`
+    pub fn new(start: usize, stop: Ix<'l>) -> Self {
+        stop.with(|i, lt| {
+            Self::new_with(start, i, LessEqual::from(lt))
+        })
+    }
+
+    /// `[0 .. stop)`
+    pub fn new_to(stop: Ix<'l>) -> Self {
+        Self::new(Zero::zero(), stop)
+    }
+
+    /// `[start ... stop]`
+    pub fn new_inclusive(start: usize, stop_inclusive: Ix<'l>) -> Self {
+        stop_inclusive.with(|i, lt| {
+            arith::succ(&i, lt, |i_plus_1, _, le| {
+                Self::new_with(start, i_plus_1, le)
+            })
+        })
+    }
+
+    /// `[0 .. stop]`
+    pub fn new_to_inclusive(stop_inclusive: Ix<'l>) -> Self {
+        Self::new_inclusive(Zero::zero(), stop_inclusive)
+    }
+
+    /// `[start .. len)`
+    pub fn new_from(start: usize, len: Val<'l, usize>) -> Self {
+        Self::new_with(start, len, LessEqual::from(Equal::refl()))
+    }
+
+    /// `[0 .. len)`
+    pub fn new_full(len: Val<'l, usize>) -> Self {
+        Self::new_from(Zero::zero(), len)
+    }
+}
+
+impl<'l> Deref for IxRange<'l> {
+    type Target = Range<usize>;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'l> DerefMut for IxRange<'l> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<'l> Iterator for IxRange<'l> {
+    type Item = Ix<'l>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        (**self).next().map(|i| unsafe { Ix::from_raw(i) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+}
+
+impl<'l> DoubleEndedIterator for IxRange<'l> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (**self).next_back().map(|i| unsafe { Ix::from_raw(i) })
+    }
+}
+
+/// Calls `f` with every index below `'l` in ascending order.
+///
+/// Since the indices are branded, `f` can use them on any number of slices
+/// of length `'l` without bounds checks.
+pub fn for_each_indexed<'l, F>(len: Val<'l, usize>, f: F)
+    where F: FnMut(Ix<'l>) {
+    IxRange::new_full(len).for_each(f)
+}
+
+/// Iterates over two slices of the same length in lockstep.
+pub fn zip<'a, 'b, 'l, T, U>(a: Sl<'a, 'l, T>, b: Sl<'b, 'l, U>,
+                             len: Val<'l, usize>) -> Zip<'a, 'b, 'l, T, U> {
+    Zip { a, b, range: IxRange::new_full(len) }
+}
+
+/// Like [`zip`](fn.zip.html), but the first slice is mutable.
+pub fn zip_mut<'a, 'b, 'l, T, U>(a: MutSl<'a, 'l, T>, b: Sl<'b, 'l, U>,
+                                 len: Val<'l, usize>)
+                                 -> ZipMut<'a, 'b, 'l, T, U> {
+    ZipMut { a, b, range: IxRange::new_full(len) }
+}
+
+/// Iterates over three slices of the same length in lockstep.
+pub fn zip3<'a, 'b, 'c, 'l, T, U, V>(a: Sl<'a, 'l, T>,
+                                     b: Sl<'b, 'l, U>,
+                                     c: Sl<'c, 'l, V>,
+                                     len: Val<'l, usize>)
+                                     -> Zip3<'a, 'b, 'c, 'l, T, U, V> {
+    Zip3 { a, b, c, range: IxRange::new_full(len) }
+}
+
+/// Combines two slices of the same length elementwise.
+pub fn zip_with<'a, 'b, 'l, T, U, V, F>(a: Sl<'a, 'l, T>,
+                                        b: Sl<'b, 'l, U>,
+                                        len: Val<'l, usize>,
+                                        mut f: F) -> BoxedSl<'l, V>
+    where F: FnMut(&'a T, &'b U) -> V {
+    let vec: Vec<_> = zip(a, b, len).map(|(x, y)| f(x, y)).collect();
+    unsafe { BoxedSl::from_raw(vec.into_boxed_slice()) }
+}
+
+/// An iterator over two slices of the same length.
+pub struct Zip<'a, 'b, 'l, T: 'a, U: 'b> {
+    a: Sl<'a, 'l, T>,
+    b: Sl<'b, 'l, U>,
+    range: IxRange<'l>,
+}
+
+impl<'a, 'b, 'l, T, U> Clone for Zip<'a, 'b, 'l, T, U> {
+    fn clone(&self) -> Self {
+        Zip { a: self.a, b: self.b, range: self.range.clone() }
+    }
+}
+
+impl<'a, 'b, 'l, T, U> Iterator for Zip<'a, 'b, 'l, T, U> {
+    type Item = (&'a T, &'b U);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| (self.a.get(i), self.b.get(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, 'b, 'l, T, U> DoubleEndedIterator for Zip<'a, 'b, 'l, T, U> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| (self.a.get(i), self.b.get(i)))
+    }
+}
+
+impl<'a, 'b, 'l, T, U> ExactSizeIterator for Zip<'a, 'b, 'l, T, U> {}
+
+/// A mutable variant of `Zip`.
+pub struct ZipMut<'a, 'b, 'l, T: 'a, U: 'b> {
+    a: MutSl<'a, 'l, T>,
+    b: Sl<'b, 'l, U>,
+    range: IxRange<'l>,
+}
+
+impl<'a, 'b, 'l, T, U> Iterator for ZipMut<'a, 'b, 'l, T, U> {
+    type Item = (&'a mut T, &'b U);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // each index is visited at most once
+        self.range.next().map(|i| (self.a.get_mut(i), self.b.get(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, 'b, 'l, T, U> DoubleEndedIterator for ZipMut<'a, 'b, 'l, T, U> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| (self.a.get_mut(i), self.b.get(i)))
+    }
+}
+
+impl<'a, 'b, 'l, T, U> ExactSizeIterator for ZipMut<'a, 'b, 'l, T, U> {}
+
+/// An iterator over three slices of the same length.
+pub struct Zip3<'a, 'b, 'c, 'l, T: 'a, U: 'b, V: 'c> {
+    a: Sl<'a, 'l, T>,
+    b: Sl<'b, 'l, U>,
+    c: Sl<'c, 'l, V>,
+    range: IxRange<'l>,
+}
+
+impl<'a, 'b, 'c, 'l, T, U, V> Clone for Zip3<'a, 'b, 'c, 'l, T, U, V> {
+    fn clone(&self) -> Self {
+        Zip3 { a: self.a, b: self.b, c: self.c, range: self.range.clone() }
+    }
+}
+
+impl<'a, 'b, 'c, 'l, T, U, V> Iterator for Zip3<'a, 'b, 'c, 'l, T, U, V> {
+    type Item = (&'a T, &'b U, &'c V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next()
+            .map(|i| (self.a.get(i), self.b.get(i), self.c.get(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, 'b, 'c, 'l, T, U, V> DoubleEndedIterator
+    for Zip3<'a, 'b, 'c, 'l, T, U, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back()
+            .map(|i| (self.a.get(i), self.b.get(i), self.c.get(i)))
+    }
+}
+
+impl<'a, 'b, 'c, 'l, T, U, V> ExactSizeIterator
+    for Zip3<'a, 'b, 'c, 'l, T, U, V> {}
+
+/// A bijection on `Ix<'l>`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Permutation<'l> {
+    map: BoxedSl<'l, Ix<'l>>,
+}
+
+impl<'l> Permutation<'l> {
+    /// Checks whether the slice maps every index to a distinct index.
+    pub fn from_boxed_sl(map: BoxedSl<'l, Ix<'l>>)
+                         -> Result<Self, BoxedSl<'l, Ix<'l>>> {
+        let mut seen = BoxedSl::new(map.len(), false);
+        for &i in map.iter() {
+            if seen[i] {
+                return Err(map);
+            }
+            seen[i] = true;
+        }
+        Ok(Permutation { map })
+    }
+
+    pub fn identity(len: Val<'l, usize>) -> Self {
+        let map = IxRange::new_full(len).collect::<Vec<_>>();
+        Permutation {
+            map: unsafe { BoxedSl::from_raw(map.into_boxed_slice()) },
+        }
+    }
+
+    /// The permutation that sorts the slice, i.e. the one for which
+    /// `permute` rearranges the elements into ascending order.
+    pub fn argsort<'a, T: Ord>(sl: SlLen<'a, 'l, T>) -> Self {
+        let mut map = Permutation::identity(sl.len()).map;
+        map.sort_by_key(|&i| &sl[i]);
+        Permutation { map }
+    }
+
+    pub fn len(&self) -> Val<'l, usize> {
+        self.map.len()
+    }
+
+    pub fn apply(&self, index: Ix<'l>) -> Ix<'l> {
+        self.map[index]
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut inverse = self.map.clone();
+        for (i, &j) in self.map.iter_enumerated() {
+            inverse[j] = i;
+        }
+        Permutation { map: inverse }
+    }
+
+    /// Returns the permutation that applies `other` first, followed by
+    /// `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        let mut map = other.map.clone();
+        for i in map.iter_mut() {
+            *i = self.apply(*i);
+        }
+        Permutation { map }
+    }
+
+    /// Rearranges the slice in place so that the element at `i` is moved
+    /// to the index `j` for which `i == self.apply(j)`.
+    pub fn permute<'a, T>(&self, mut sl: MutSl<'a, 'l, T>) {
+        let mut visited = BoxedSl::new(self.len(), false);
+        for start in IxRange::new_full(self.len()) {
+            let mut i = start;
+            while !visited[i] {
+                visited[i] = true;
+                let j = self.apply(i);
+                if j == start {
+                    break;
+                }
+                sl.swap(i, j);
+                i = j;
+            }
+        }
+    }
+
+    pub fn as_boxed_sl(&self) -> &BoxedSl<'l, Ix<'l>> {
+        &self.map
+    }
+
+    pub fn into_boxed_sl(self) -> BoxedSl<'l, Ix<'l>> {
+        self.map
+    }
+}
+
+impl<'l> fmt::Debug for Permutation<'l> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Permutation(")?;
+        f.debug_list().entries(self.map.iter().map(|i| **i)).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'l> Index<Ix<'l>> for Permutation<'l> {
+    type Output = Ix<'l>;
+    fn index(&self, index: Ix<'l>) -> &Self::Output {
+        &self.map[index]
+    }
+}
+
+/// An owned two-dimensional array with `'r` rows and `'c` columns, stored
+/// in row-major order.
+pub struct Matrix<'r, 'c, T> {
+    rows: Val<'r, usize>,
+    cols: Val<'c, usize>,
+    inner: Box<[T]>,
+}
+
+impl<'r, 'c, T> Matrix<'r, 'c, T> {
+    /// Returns `None` if the number of elements would overflow.
+    pub fn new(rows: Val<'r, usize>, cols: Val<'c, usize>, value: T)
+               -> Option<Self> where T: Clone {
+        arith::mul(&rows, &cols, |len, product| {
+            Matrix::from_boxed_sl(BoxedSl::new(len, value), rows, cols,
+                                  product)
+        })
+    }
+
+    /// Returns `None` if the number of elements would overflow.
+    pub fn from_fn<F>(rows: Val<'r, usize>, cols: Val<'c, usize>, mut f: F)
+                      -> Option<Self>
+        where F: FnMut(Ix<'r>, Ix<'c>) -> T {
+        arith::mul(&rows, &cols, |len, product| {
+            let mut vec = Vec::with_capacity(len.value());
+            for i in IxRange::new_full(rows) {
+                for j in IxRange::new_full(cols) {
+                    vec.push(f(i, j));
+                }
+            }
+            let boxed_sl =
+                unsafe { BoxedSl::from_raw(vec.into_boxed_slice()) };
+            Matrix::from_boxed_sl(boxed_sl, rows, cols, product)
+        })
+    }
+
+    /// Interprets a flat slice of length `'r * 'c` in row-major order.
+    pub fn from_boxed_sl<'l>(boxed_sl: BoxedSl<'l, T>,
+                             rows: Val<'r, usize>,
+                             cols: Val<'c, usize>,
+                             _: Product<Val<'r, usize>,
+                                        Val<'c, usize>,
+                                        Val<'l, usize>>) -> Self {
+        Matrix { rows, cols, inner: boxed_sl.into_inner() }
+    }
+
+    pub fn rows(&self) -> Val<'r, usize> {
+        self.rows
+    }
+
+    pub fn cols(&self) -> Val<'c, usize> {
+        self.cols
+    }
+
+    pub fn view<'a>(&'a self) -> MatrixView<'a, 'r, 'c, T> {
+        MatrixView {
+            marker: PhantomData,
+            rows: self.rows,
+            cols: self.cols,
+            ptr: self.inner.as_ptr(),
+            row_stride: self.cols.value(),
+            col_stride: 1,
+        }
+    }
+
+    pub fn view_mut<'a>(&'a mut self) -> MatrixViewMut<'a, 'r, 'c, T> {
+        MatrixViewMut {
+            marker: PhantomData,
+            rows: self.rows,
+            cols: self.cols,
+            ptr: self.inner.as_mut_ptr(),
+            row_stride: self.cols.value(),
+            col_stride: 1,
+        }
+    }
+
+    /// Rows are contiguous, so they can be viewed as slices.
+    pub fn row<'a>(&'a self, i: Ix<'r>) -> Sl<'a, 'c, T> {
+        unsafe { Sl::from_raw(self.inner.as_ptr().add(*i * *self.cols)) }
+    }
+
+    /// Rows are contiguous, so they can be viewed as slices.
+    pub fn row_mut<'a>(&'a mut self, i: Ix<'r>) -> MutSl<'a, 'c, T> {
+        let offset = *i * *self.cols;
+        unsafe { MutSl::from_raw(self.inner.as_mut_ptr().add(offset)) }
+    }
+
+    pub fn as_strided<'a>(&'a self) -> Strided<'a, 'r, 'c, 'c, T> {
+        unsafe { Strided::from_raw(self.inner.as_ptr(), self.rows,
+                                   self.cols, self.cols) }
+    }
+
+    pub fn as_strided_mut<'a>(&'a mut self)
+                              -> StridedMut<'a, 'r, 'c, 'c, T> {
+        unsafe { StridedMut::from_raw(self.inner.as_mut_ptr(), self.rows,
+                                      self.cols, self.cols) }
+    }
+
+    /// Flattens the matrix into a slice of length `'r * 'c`.
+    pub fn into_boxed_sl<F, R>(self, callback: F) -> R
+        where F: for<'l> FnOnce(BoxedSl<'l, T>,
+                                Product<Val<'r, usize>,
+                                        Val<'c, usize>,
+                                        Val<'l, usize>>) -> R {
+        // every constructor checks `inner.len() == 'r * 'c`, and the fresh
+        // brand `'l` comes from the callback's signature
+        unsafe { callback(BoxedSl::from_raw(self.inner), Product::conjure()) }
+    }
+}
+
+impl<'r, 'c, T: Clone> Clone for Matrix<'r, 'c, T> {
+    fn clone(&self) -> Self {
+        Matrix { rows: self.rows, cols: self.cols, inner: self.inner.clone() }
+    }
+}
+
+impl<'r, 'c, T: fmt::Debug> fmt::Debug for Matrix<'r, 'c, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Matrix(")?;
+        f.debug_list().entries(self.view().iter_rows()).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'r, 'c, T> Index<(Ix<'r>, Ix<'c>)> for Matrix<'r, 'c, T> {
+    type Output = T;
+    fn index(&self, (i, j): (Ix<'r>, Ix<'c>)) -> &Self::Output {
+        unsafe { self.inner.get_unchecked(*i * *self.cols + *j) }
+    }
+}
+
+impl<'r, 'c, T> IndexMut<(Ix<'r>, Ix<'c>)> for Matrix<'r, 'c, T> {
+    fn index_mut(&mut self, (i, j): (Ix<'r>, Ix<'c>)) -> &mut Self::Output {
+        let offset = *i * *self.cols + *j;
+        unsafe { self.inner.get_unchecked_mut(offset) }
+    }
+}
+
+/// A borrowed view of a `'r`-by-`'c` matrix.
+///
+/// Elements need not be stored in row-major order: for example,
+/// `transpose` returns a column-major view of the same elements.
+pub struct MatrixView<'a, 'r, 'c, T: 'a> {
+    marker: PhantomData<&'a T>,
+    rows: Val<'r, usize>,
+    cols: Val<'c, usize>,
+    ptr: *const T,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+impl<'a, 'r, 'c, T> Clone for MatrixView<'a, 'r, 'c, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, 'r, 'c, T> Copy for MatrixView<'a, 'r, 'c, T> {}
+
+impl<'a, 'r, 'c, T> MatrixView<'a, 'r, 'c, T> {
+    pub fn rows(&self) -> Val<'r, usize> {
+        self.rows
+    }
+
+    pub fn cols(&self) -> Val<'c, usize> {
+        self.cols
+    }
+
+    fn get(self, i: Ix<'r>, j: Ix<'c>) -> &'a T {
+        let offset = *i * self.row_stride + *j * self.col_stride;
+        unsafe { &*self.ptr.add(offset) }
+    }
+
+    /// Swaps the roles of rows and columns.
+    pub fn transpose(self) -> MatrixView<'a, 'c, 'r, T> {
+        MatrixView {
+            marker: PhantomData,
+            rows: self.cols,
+            cols: self.rows,
+            ptr: self.ptr,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    pub fn row(self, i: Ix<'r>) -> Line<'a, 'c, T> {
+        Line {
+            marker: PhantomData,
+            len: self.cols,
+            ptr: unsafe { self.ptr.add(*i * self.row_stride) },
+            stride: self.col_stride,
+        }
+    }
+
+    pub fn col(self, j: Ix<'c>) -> Line<'a, 'r, T> {
+        self.transpose().row(j)
+    }
+
+    pub fn iter_rows(self) -> Lines<'a, 'r, 'c, T> {
+        Lines { view: self, range: IxRange::new_full(self.rows) }
+    }
+
+    pub fn iter_cols(self) -> Lines<'a, 'c, 'r, T> {
+        self.transpose().iter_rows()
+    }
+}
+
+impl<'a, 'r, 'c, T: fmt::Debug> fmt::Debug for MatrixView<'a, 'r, 'c, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("MatrixView(")?;
+        f.debug_list().entries(self.iter_rows()).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'r, 'c, T> Index<(Ix<'r>, Ix<'c>)> for MatrixView<'a, 'r, 'c, T> {
+    type Output = T;
+    fn index(&self, (i, j): (Ix<'r>, Ix<'c>)) -> &Self::Output {
+        self.get(i, j)
+    }
+}
+
+/// A mutable variant of `MatrixView`.
+pub struct MatrixViewMut<'a, 'r, 'c, T: 'a> {
+    marker: PhantomData<&'a mut T>,
+    rows: Val<'r, usize>,
+    cols: Val<'c, usize>,
+    ptr: *mut T,
+    row_stride: usize,
+    col_stride: usize,
+}
+
+impl<'a, 'r, 'c, T> MatrixViewMut<'a, 'r, 'c, T> {
+    pub fn rows(&self) -> Val<'r, usize> {
+        self.rows
+    }
+
+    pub fn cols(&self) -> Val<'c, usize> {
+        self.cols
+    }
+
+    pub fn as_view<'b>(&'b self) -> MatrixView<'b, 'r, 'c, T> {
+        MatrixView {
+            marker: PhantomData,
+            rows: self.rows,
+            cols: self.cols,
+            ptr: self.ptr,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    /// Swaps the roles of rows and columns.
+    pub fn transpose(self) -> MatrixViewMut<'a, 'c, 'r, T> {
+        MatrixViewMut {
+            marker: PhantomData,
+            rows: self.cols,
+            cols: self.rows,
+            ptr: self.ptr,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    pub fn row_mut<'b>(&'b mut self, i: Ix<'r>) -> LineMut<'b, 'c, T> {
+        LineMut {
+            marker: PhantomData,
+            len: self.cols,
+            ptr: unsafe { self.ptr.add(*i * self.row_stride) },
+            stride: self.col_stride,
+        }
+    }
+
+    pub fn col_mut<'b>(&'b mut self, j: Ix<'c>) -> LineMut<'b, 'r, T> {
+        LineMut {
+            marker: PhantomData,
+            len: self.rows,
+            ptr: unsafe { self.ptr.add(*j * self.col_stride) },
+            stride: self.row_stride,
+        }
+    }
+
+    pub fn iter_rows_mut<'b>(&'b mut self) -> LinesMut<'b, 'r, 'c, T> {
+        LinesMut {
+            marker: PhantomData,
+            cols: self.cols,
+            ptr: self.ptr,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+            range: IxRange::new_full(self.rows),
+        }
+    }
+
+    pub fn iter_cols_mut<'b>(&'b mut self) -> LinesMut<'b, 'c, 'r, T> {
+        LinesMut {
+            marker: PhantomData,
+            cols: self.rows,
+            ptr: self.ptr,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+            range: IxRange::new_full(self.cols),
+        }
+    }
+}
+
+impl<'a, 'r, 'c, T: fmt::Debug> fmt::Debug for MatrixViewMut<'a, 'r, 'c, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("MatrixViewMut(")?;
+        f.debug_list().entries(self.as_view().iter_rows()).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'r, 'c, T> Index<(Ix<'r>, Ix<'c>)> for MatrixViewMut<'a, 'r, 'c, T> {
+    type Output = T;
+    fn index(&self, (i, j): (Ix<'r>, Ix<'c>)) -> &Self::Output {
+        self.as_view().get(i, j)
+    }
+}
+
+impl<'a, 'r, 'c, T> IndexMut<(Ix<'r>, Ix<'c>)>
+    for MatrixViewMut<'a, 'r, 'c, T> {
+    fn index_mut(&mut self, (i, j): (Ix<'r>, Ix<'c>)) -> &mut Self::Output {
+        let offset = *i * self.row_stride + *j * self.col_stride;
+        unsafe { &mut *self.ptr.add(offset) }
+    }
+}
+
+/// A row or column of a `MatrixView`, which need not be contiguous.
+pub struct Line<'a, 'n, T: 'a> {
+    marker: PhantomData<&'a T>,
+    len: Val<'n, usize>,
+    ptr: *const T,
+    stride: usize,
+}
+
+impl<'a, 'n, T> Clone for Line<'a, 'n, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, 'n, T> Copy for Line<'a, 'n, T> {}
+
+impl<'a, 'n, T> Line<'a, 'n, T> {
+    pub fn len(&self) -> Val<'n, usize> {
+        self.len
+    }
+
+    fn get(self, index: Ix<'n>) -> &'a T {
+        unsafe { &*self.ptr.add(*index * self.stride) }
+    }
+
+    pub fn iter(self) -> LineIter<'a, 'n, T> {
+        LineIter { line: self, range: IxRange::new_full(self.len) }
+    }
+}
+
+impl<'a, 'n, T: fmt::Debug> fmt::Debug for Line<'a, 'n, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, 'n, T> Index<Ix<'n>> for Line<'a, 'n, T> {
+    type Output = T;
+    fn index(&self, index: Ix<'n>) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<'a, 'n, T> IntoIterator for Line<'a, 'n, T> {
+    type Item = &'a T;
+    type IntoIter = LineIter<'a, 'n, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A mutable variant of `Line`.
+pub struct LineMut<'a, 'n, T: 'a> {
+    marker: PhantomData<&'a mut T>,
+    len: Val<'n, usize>,
+    ptr: *mut T,
+    stride: usize,
+}
+
+impl<'a, 'n, T> LineMut<'a, 'n, T> {
+    pub fn len(&self) -> Val<'n, usize> {
+        self.len
+    }
+
+    pub fn as_line<'b>(&'b self) -> Line<'b, 'n, T> {
+        Line {
+            marker: PhantomData,
+            len: self.len,
+            ptr: self.ptr,
+            stride: self.stride,
+        }
+    }
+}
+
+impl<'a, 'n, T> Index<Ix<'n>> for LineMut<'a, 'n, T> {
+    type Output = T;
+    fn index(&self, index: Ix<'n>) -> &Self::Output {
+        self.as_line().get(index)
+    }
+}
+
+impl<'a, 'n, T> IndexMut<Ix<'n>> for LineMut<'a, 'n, T> {
+    fn index_mut(&mut self, index: Ix<'n>) -> &mut Self::Output {
+        unsafe { &mut *self.ptr.add(*index * self.stride) }
+    }
+}
+
+/// An iterator over the elements of a `Line`.
+#[derive(Clone)]
+pub struct LineIter<'a, 'n, T: 'a> {
+    line: Line<'a, 'n, T>,
+    range: IxRange<'n>,
+}
+
+impl<'a, 'n, T> Iterator for LineIter<'a, 'n, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| self.line.get(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, 'n, T> DoubleEndedIterator for LineIter<'a, 'n, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| self.line.get(i))
+    }
+}
+
+/// An iterator over the rows (or columns) of a `MatrixView`.
+#[derive(Clone)]
+pub struct Lines<'a, 'r, 'c, T: 'a> {
+    view: MatrixView<'a, 'r, 'c, T>,
+    range: IxRange<'r>,
+}
+
+impl<'a, 'r, 'c, T> Iterator for Lines<'a, 'r, 'c, T> {
+    type Item = Line<'a, 'c, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| self.view.row(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, 'r, 'c, T> DoubleEndedIterator for Lines<'a, 'r, 'c, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| self.view.row(i))
+    }
+}
+
+/// An iterator over the rows (or columns) of a `MatrixViewMut`.
+pub struct LinesMut<'a, 'r, 'c, T: 'a> {
+    marker: PhantomData<&'a mut T>,
+    cols: Val<'c, usize>,
+    ptr: *mut T,
+    row_stride: usize,
+    col_stride: usize,
+    range: IxRange<'r>,
+}
+
+impl<'a, 'r, 'c, T> LinesMut<'a, 'r, 'c, T> {
+    fn line(&self, i: Ix<'r>) -> LineMut<'a, 'c, T> {
+        // distinct rows of a mutable view never overlap, and each row is
+        // handed out at most once
+        LineMut {
+            marker: PhantomData,
+            len: self.cols,
+            ptr: unsafe { self.ptr.add(*i * self.row_stride) },
+            stride: self.col_stride,
+        }
+    }
+}
+
+impl<'a, 'r, 'c, T> Iterator for LinesMut<'a, 'r, 'c, T> {
+    type Item = LineMut<'a, 'c, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| self.line(i))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, 'r, 'c, T> DoubleEndedIterator for LinesMut<'a, 'r, 'c, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|i| self.line(i))
+    }
+}
+
+/// A `'r`-by-`'c` view into a row-major buffer whose rows are `'s` elements
+/// apart, where `'c <= 's`.
+///
+/// The stride (also known as the "leading dimension") is stored in the
+/// view, which allows column ranges and sub-blocks of a larger buffer to be
+/// indexed without bounds checks.
+pub struct Strided<'a, 'r, 'c, 's, T: 'a> {
+    marker: PhantomData<&'a T>,
+    ptr: *const T,
+    rows: Val<'r, usize>,
+    cols: Val<'c, usize>,
+    stride: Val<'s, usize>,
+}
+
+impl<'a, 'r, 'c, 's, T> Clone for Strided<'a, 'r, 'c, 's, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, 'r, 'c, 's, T> Copy for Strided<'a, 'r, 'c, 's, T> {}
+
+impl<'a, 'r, 'c, 's, T> Strided<'a, 'r, 'c, 's, T> {
+    unsafe fn from_raw(ptr: *const T,
+                       rows: Val<'r, usize>,
+                       cols: Val<'c, usize>,
+                       stride: Val<'s, usize>) -> Self {
+        Strided { marker: PhantomData, ptr, rows, cols, stride }
+    }
+
+    pub fn new<'l>(sl: Sl<'a, 'l, T>,
+                   rows: Val<'r, usize>,
+                   cols: Val<'c, usize>,
+                   stride: Val<'s, usize>,
+                   _: LessEqual<Val<'c, usize>, Val<'s, usize>>,
+                   _: Product<Val<'r, usize>,
+                              Val<'s, usize>,
+                              Val<'l, usize>>) -> Self {
+        unsafe { Strided::from_raw(sl.ptr, rows, cols, stride) }
+    }
+
+    pub fn rows(&self) -> Val<'r, usize> {
+        self.rows
+    }
+
+    pub fn cols(&self) -> Val<'c, usize> {
+        self.cols
+    }
+
+    pub fn stride(&self) -> Val<'s, usize> {
+        self.stride
+    }
+
+    pub fn as_view(self) -> MatrixView<'a, 'r, 'c, T> {
+        MatrixView {
+            marker: PhantomData,
+            rows: self.rows,
+            cols: self.cols,
+            ptr: self.ptr,
+            row_stride: self.stride.value(),
+            col_stride: 1,
+        }
+    }
+
+    pub fn row(self, i: Ix<'r>) -> Sl<'a, 'c, T> {
+        unsafe { Sl::from_raw(self.ptr.add(*i * *self.stride)) }
+    }
+
+    pub fn col(self, j: Ix<'c>) -> Line<'a, 'r, T> {
+        self.as_view().col(j)
+    }
+
+    /// Splits the view into the rows before `mid` and the rows from `mid`
+    /// onward.
+    pub fn split_rows<F, R>(self, mid: Ix<'r>, callback: F) -> R
+        where F: for<'m, 'n> FnOnce(Strided<'a, 'm, 'c, 's, T>,
+                                    Strided<'a, 'n, 'c, 's, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'r, usize>>) -> R {
+        mid.with(|m, lt| {
+            arith::sub(&self.rows, &m, LessEqual::from(lt), |n, diff| unsafe {
+                let ptr = self.ptr.add(*m * *self.stride);
+                callback(Strided::from_raw(self.ptr, m, self.cols,
+                                           self.stride),
+                         Strided::from_raw(ptr, n, self.cols, self.stride),
+                         diff.into_sum().comm())
+            })
+        })
+    }
+
+    /// Splits the view into the columns before `mid` and the columns from
+    /// `mid` onward.  Both halves keep the original stride.
+    pub fn split_cols<F, R>(self, mid: Ix<'c>, callback: F) -> R
+        where F: for<'m, 'n> FnOnce(Strided<'a, 'r, 'm, 's, T>,
+                                    Strided<'a, 'r, 'n, 's, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'c, usize>>) -> R {
+        mid.with(|m, lt| {
+            arith::sub(&self.cols, &m, LessEqual::from(lt), |n, diff| unsafe {
+                let ptr = self.ptr.add(*m);
+                callback(Strided::from_raw(self.ptr, self.rows, m,
+                                           self.stride),
+                         Strided::from_raw(ptr, self.rows, n, self.stride),
+                         diff.into_sum().comm())
+            })
+        })
+    }
+}
+
+impl<'a, 'r, 'c, 's, T: fmt::Debug> fmt::Debug
+    for Strided<'a, 'r, 'c, 's, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Strided(")?;
+        f.debug_list().entries(self.as_view().iter_rows()).finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'r, 'c, 's, T> Index<(Ix<'r>, Ix<'c>)>
+    for Strided<'a, 'r, 'c, 's, T> {
+    type Output = T;
+    fn index(&self, (i, j): (Ix<'r>, Ix<'c>)) -> &Self::Output {
+        unsafe { &*self.ptr.add(*i * *self.stride + *j) }
+    }
+}
+
+/// A mutable variant of `Strided`.
+pub struct StridedMut<'a, 'r, 'c, 's, T: 'a> {
+    marker: PhantomData<&'a mut T>,
+    ptr: *mut T,
+    rows: Val<'r, usize>,
+    cols: Val<'c, usize>,
+    stride: Val<'s, usize>,
+}
+
+impl<'a, 'r, 'c, 's, T> StridedMut<'a, 'r, 'c, 's, T> {
+    unsafe fn from_raw(ptr: *mut T,
+                       rows: Val<'r, usize>,
+                       cols: Val<'c, usize>,
+                       stride: Val<'s, usize>) -> Self {
+        StridedMut { marker: PhantomData, ptr, rows, cols, stride }
+    }
+
+    pub fn new<'l>(sl: MutSl<'a, 'l, T>,
+                   rows: Val<'r, usize>,
+                   cols: Val<'c, usize>,
+                   stride: Val<'s, usize>,
+                   _: LessEqual<Val<'c, usize>, Val<'s, usize>>,
+                   _: Product<Val<'r, usize>,
+                              Val<'s, usize>,
+                              Val<'l, usize>>) -> Self {
+        unsafe { StridedMut::from_raw(sl.ptr, rows, cols, stride) }
+    }
+
+    pub fn rows(&self) -> Val<'r, usize> {
+        self.rows
+    }
+
+    pub fn cols(&self) -> Val<'c, usize> {
+        self.cols
+    }
+
+    pub fn stride(&self) -> Val<'s, usize> {
+        self.stride
+    }
+
+    pub fn as_strided<'b>(&'b self) -> Strided<'b, 'r, 'c, 's, T> {
+        unsafe { Strided::from_raw(self.ptr, self.rows, self.cols,
+                                   self.stride) }
+    }
+
+    pub fn row_mut<'b>(&'b mut self, i: Ix<'r>) -> MutSl<'b, 'c, T> {
+        unsafe { MutSl::from_raw(self.ptr.add(*i * *self.stride)) }
+    }
+
+    pub fn col_mut<'b>(&'b mut self, j: Ix<'c>) -> LineMut<'b, 'r, T> {
+        LineMut {
+            marker: PhantomData,
+            len: self.rows,
+            ptr: unsafe { self.ptr.add(*j) },
+            stride: self.stride.value(),
+        }
+    }
+
+    /// See [`Strided::split_rows`](struct.Strided.html#method.split_rows).
+    pub fn split_rows_mut<F, R>(self, mid: Ix<'r>, callback: F) -> R
+        where F: for<'m, 'n> FnOnce(StridedMut<'a, 'm, 'c, 's, T>,
+                                    StridedMut<'a, 'n, 'c, 's, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'r, usize>>) -> R {
+        mid.with(|m, lt| {
+            arith::sub(&self.rows, &m, LessEqual::from(lt), |n, diff| unsafe {
+                let ptr = self.ptr.add(*m * *self.stride);
+                callback(StridedMut::from_raw(self.ptr, m, self.cols,
+                                              self.stride),
+                         StridedMut::from_raw(ptr, n, self.cols, self.stride),
+                         diff.into_sum().comm())
+            })
+        })
+    }
+
+    /// See [`Strided::split_cols`](struct.Strided.html#method.split_cols).
+    pub fn split_cols_mut<F, R>(self, mid: Ix<'c>, callback: F) -> R
+        where F: for<'m, 'n> FnOnce(StridedMut<'a, 'r, 'm, 's, T>,
+                                    StridedMut<'a, 'r, 'n, 's, T>,
+                                    Sum<Val<'m, usize>,
+                                        Val<'n, usize>,
+                                        Val<'c, usize>>) -> R {
+        mid.with(|m, lt| {
+            arith::sub(&self.cols, &m, LessEqual::from(lt), |n, diff| unsafe {
+                let ptr = self.ptr.add(*m);
+                callback(StridedMut::from_raw(self.ptr, self.rows, m,
+                                              self.stride),
+                         StridedMut::from_raw(ptr, self.rows, n, self.stride),
+                         diff.into_sum().comm())
+            })
+        })
+    }
+}
+
+impl<'a, 'r, 'c, 's, T: fmt::Debug> fmt::Debug
+    for StridedMut<'a, 'r, 'c, 's, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("StridedMut(")?;
+        f.debug_list()
+            .entries(self.as_strided().as_view().iter_rows())
+            .finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'r, 'c, 's, T> Index<(Ix<'r>, Ix<'c>)>
+    for StridedMut<'a, 'r, 'c, 's, T> {
+    type Output = T;
+    fn index(&self, (i, j): (Ix<'r>, Ix<'c>)) -> &Self::Output {
+        unsafe { &*self.ptr.add(*i * *self.stride + *j) }
+    }
+}
+
+impl<'a, 'r, 'c, 's, T> IndexMut<(Ix<'r>, Ix<'c>)>
+    for StridedMut<'a, 'r, 'c, 's, T> {
+    fn index_mut(&mut self, (i, j): (Ix<'r>, Ix<'c>)) -> &mut Self::Output {
+        unsafe { &mut *self.ptr.add(*i * *self.stride + *j) }
+    }
+}
+
+/// A store that can be indexed by `I`, with a checked accessor.
+///
+/// # Safety
+///
+/// Whenever `get` returns `Some`, indexing with the same index must not
+/// panic and must return the same element.  For an index that carries its
+/// own bounds proof, such as `Ix<'l>` for a store of length `'l`, `get`
+/// must always return `Some`.
+pub unsafe trait Get<I>: Index<I> {
+    fn get(&self, index: I) -> Option<&Self::Output>;
+}
+
+/// A mutable variant of `Get`.
+///
+/// # Safety
+///
+/// The same as for `Get`, with `get_mut` and mutable indexing.
+pub unsafe trait GetMut<I>: Get<I> + IndexMut<I> {
+    fn get_mut(&mut self, index: I) -> Option<&mut Self::Output>;
+}
+
+unsafe impl<'l, T> Get<Ix<'l>> for BoxedSl<'l, T> {
+    fn get(&self, index: Ix<'l>) -> Option<&T> {
+        Some(&self[index])
+    }
+}
+
+unsafe impl<'l, T> GetMut<Ix<'l>> for BoxedSl<'l, T> {
+    fn get_mut(&mut self, index: Ix<'l>) -> Option<&mut T> {
+        Some(&mut self[index])
+    }
+}
+
+unsafe impl<'l, T> Get<Ix<'l>> for BrandedVec<'l, T> {
+    fn get(&self, index: Ix<'l>) -> Option<&T> {
+        Some(&self[index])
+    }
+}
+
+unsafe impl<'l, T> GetMut<Ix<'l>> for BrandedVec<'l, T> {
+    fn get_mut(&mut self, index: Ix<'l>) -> Option<&mut T> {
+        Some(&mut self[index])
+    }
+}
+
+unsafe impl<'a, 'l, T> Get<Ix<'l>> for Sl<'a, 'l, T> {
+    fn get(&self, index: Ix<'l>) -> Option<&T> {
+        Some(&self[index])
+    }
+}
+
+unsafe impl<'a, 'l, T> Get<Ix<'l>> for SlLen<'a, 'l, T> {
+    fn get(&self, index: Ix<'l>) -> Option<&T> {
+        Some(&self[index])
+    }
+}
+
+unsafe impl<'a, 'l, T> Get<Ix<'l>> for MutSl<'a, 'l, T> {
+    fn get(&self, index: Ix<'l>) -> Option<&T> {
+        Some(&self[index])
+    }
+}
+
+unsafe impl<'a, 'l, T> GetMut<Ix<'l>> for MutSl<'a, 'l, T> {
+    fn get_mut(&mut self, index: Ix<'l>) -> Option<&mut T> {
+        Some(&mut self[index])
+    }
+}
+
+unsafe impl<'r, 'c, T> Get<(Ix<'r>, Ix<'c>)> for Matrix<'r, 'c, T> {
+    fn get(&self, index: (Ix<'r>, Ix<'c>)) -> Option<&T> {
+        Some(&self[index])
+    }
+}
+
+unsafe impl<'r, 'c, T> GetMut<(Ix<'r>, Ix<'c>)> for Matrix<'r, 'c, T> {
+    fn get_mut(&mut self, index: (Ix<'r>, Ix<'c>)) -> Option<&mut T> {
+        Some(&mut self[index])
+    }
+}
+
+/// A total function from keys `K` to indices (or other keys).
+///
+/// Because an `Ix<'l>` can only be obtained with evidence that it is in
+/// bounds, a `KeyFn<K, Output = Ix<'l>>` is a proof that every `K` maps to
+/// a valid index of any store branded with `'l`.
+pub trait KeyFn<K> {
+    type Output;
+
+    fn apply(&self, key: K) -> Self::Output;
+
+    /// `(X :-> Y, Y :-> Z) -> (X :-> Z)`
+    fn then<G>(self, next: G) -> Compose<Self, G>
+        where Self: Sized, G: KeyFn<Self::Output> {
+        Compose(self, next)
     }
 }
 
-impl<'a, 'l, T> IndexMut<Ix<'l>> for MutSl<'a, 'l, T> {
-    fn index_mut(&mut self, index: Ix<'l>) -> &mut Self::Output {
-        self.get_mut(index)
+impl<K, R, F: Fn(K) -> R> KeyFn<K> for F {
+    type Output = R;
+    fn apply(&self, key: K) -> Self::Output {
+        self(key)
     }
 }
 
-#[derive(Clone)]
-pub struct IxRange<'l> {
-    len: PhantomData<Val<'l, usize>>,
-    inner: Range<usize>,
+/// Applies `F` followed by `G`.
+#[derive(Clone, Copy, Debug)]
+pub struct Compose<F, G>(pub F, pub G);
+
+impl<K, F: KeyFn<K>, G: KeyFn<F::Output>> KeyFn<K> for Compose<F, G> {
+    type Output = G::Output;
+    fn apply(&self, key: K) -> Self::Output {
+        self.1.apply(self.0.apply(key))
+    }
 }
 
-impl<'l> IxRange<'l> {
-    pub fn new_with<'i>(start: usize, stop: Val<'i, usize>,
-                        _: LessEqual<Val<'i, usize>, Val<'l, usize>>) -> Self {
-        IxRange {
-            len: PhantomData,
-            inner: range(start, stop.value()),
+/// Maps `(row, col)` to the index of a `'r`-by-`'c` array stored in
+/// row-major order in a store of length `'l`.
+#[derive(Clone, Copy, Debug)]
+pub struct RowMajor<'r, 'c, 'l> {
+    cols: Val<'c, usize>,
+    product: Product<Val<'r, usize>, Val<'c, usize>, Val<'l, usize>>,
+}
+
+impl<'r, 'c, 'l> RowMajor<'r, 'c, 'l> {
+    pub fn new(cols: Val<'c, usize>,
+               product: Product<Val<'r, usize>,
+                                Val<'c, usize>,
+                                Val<'l, usize>>) -> Self {
+        RowMajor { cols, product }
+    }
+}
+
+impl<'r, 'c, 'l> KeyFn<(Ix<'r>, Ix<'c>)> for RowMajor<'r, 'c, 'l> {
+    type Output = Ix<'l>;
+    fn apply(&self, (row, col): (Ix<'r>, Ix<'c>)) -> Self::Output {
+        Ix::flatten(row, col, self.cols, self.product)
+    }
+}
+
+/// Combines an indexer with a store, so that the store can be indexed by
+/// the keys of the indexer without bounds checks.
+///
+/// An indexer that returns `Option` is partial; its keys are looked up
+/// through the checked path, `get` and `get_mut`.
+#[derive(Clone, Copy, Debug)]
+pub struct Mapping<F, S> {
+    indexer: F,
+    store: S,
+}
+
+impl<F, S> Mapping<F, S> {
+    pub fn new(indexer: F, store: S) -> Self {
+        Mapping { indexer, store }
+    }
+
+    pub fn indexer(&self) -> &F {
+        &self.indexer
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn store_mut(&mut self) -> &mut S {
+        &mut self.store
+    }
+
+    pub fn into_inner(self) -> (F, S) {
+        (self.indexer, self.store)
+    }
+
+    /// Looks up a key through a partial indexer.
+    pub fn get<K, I>(&self, key: K) -> Option<&S::Output>
+        where F: KeyFn<K, Output = Option<I>>, S: Get<I> {
+        self.indexer.apply(key).and_then(|index| self.store.get(index))
+    }
+
+    /// See [`get`](#method.get).
+    pub fn get_mut<K, I>(&mut self, key: K) -> Option<&mut S::Output>
+        where F: KeyFn<K, Output = Option<I>>, S: GetMut<I> {
+        match self.indexer.apply(key) {
+            Some(index) => self.store.get_mut(index),
+            None => None,
         }
     }
+}
 
-    /// `[start .. stop)`
-    pub fn new(start: usize, stop: Ix<'l>) -> Self {
-        stop.with(|i, lt| {
-            Self::new_with(start, i, LessEqual::from(lt))
-        })
+impl<K, F, S> Index<K> for Mapping<F, S>
+    where F: KeyFn<K>,
+          S: Get<F::Output> {
+    type Output = S::Output;
+    fn index(&self, key: K) -> &Self::Output {
+        &self.store[self.indexer.apply(key)]
     }
+}
 
-    /// `[0 .. stop)`
-    pub fn new_to(stop: Ix<'l>) -> Self {
-        Self::new(Zero::zero(), stop)
+impl<K, F, S> IndexMut<K> for Mapping<F, S>
+    where F: KeyFn<K>,
+          S: GetMut<F::Output> {
+    fn index_mut(&mut self, key: K) -> &mut Self::Output {
+        &mut self.store[self.indexer.apply(key)]
     }
+}
 
-    /// `[start ... stop]`
-    pub fn new_inclusive(start: usize, stop_inclusive: Ix<'l>) -> Self {
-        stop_inclusive.with(|i, lt| {
-            arith::succ(&i, lt, |i_plus_1, _, le| {
-                Self::new_with(start, i_plus_1, le)
-            })
-        })
+const BITS: usize = 64;
+
+/// A set of indices below `'l`, stored as a dense bitset.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IxSet<'l> {
+    len: Val<'l, usize>,
+    words: Box<[u64]>,
+}
+
+impl<'l> IxSet<'l> {
+    /// Creates an empty set.
+    pub fn new(len: Val<'l, usize>) -> Self {
+        let words = len.value().div_ceil(BITS);
+        IxSet { len, words: vec![0; words].into_boxed_slice() }
     }
 
-    /// `[0 .. stop]`
-    pub fn new_to_inclusive(stop_inclusive: Ix<'l>) -> Self {
-        Self::new_inclusive(Zero::zero(), stop_inclusive)
+    /// Creates a set containing every index below `'l`.
+    pub fn full(len: Val<'l, usize>) -> Self {
+        let mut set = IxSet::new(len);
+        set.extend(IxRange::new_full(len));
+        set
     }
 
-    /// `[start .. len)`
-    pub fn new_from(start: usize, len: Val<'l, usize>) -> Self {
-        Self::new_with(start, len, LessEqual::from(Equal::refl()))
+    pub fn capacity(&self) -> Val<'l, usize> {
+        self.len
     }
 
-    /// `[0 .. len)`
-    pub fn new_full(len: Val<'l, usize>) -> Self {
-        Self::new_from(Zero::zero(), len)
+    fn word(&self, index: Ix<'l>) -> &u64 {
+        // index < len implies index / BITS < words.len()
+        unsafe { self.words.get_unchecked(*index / BITS) }
+    }
+
+    fn word_mut(&mut self, index: Ix<'l>) -> &mut u64 {
+        unsafe { self.words.get_unchecked_mut(*index / BITS) }
+    }
+
+    pub fn contains(&self, index: Ix<'l>) -> bool {
+        self.word(index) & (1 << (*index % BITS)) != 0
+    }
+
+    /// Returns whether the index was newly inserted.
+    pub fn insert(&mut self, index: Ix<'l>) -> bool {
+        let mask = 1 << (*index % BITS);
+        let word = self.word_mut(index);
+        let absent = *word & mask == 0;
+        *word |= mask;
+        absent
+    }
+
+    /// Returns whether the index was present.
+    pub fn remove(&mut self, index: Ix<'l>) -> bool {
+        let mask = 1 << (*index % BITS);
+        let word = self.word_mut(index);
+        let present = *word & mask != 0;
+        *word &= !mask;
+        present
+    }
+
+    pub fn clear(&mut self) {
+        for word in self.words.iter_mut() {
+            *word = 0;
+        }
+    }
+
+    /// The number of indices in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Iterate over the indices in ascending order.
+    pub fn iter<'a>(&'a self) -> IxSetIter<'a, 'l> {
+        IxSetIter {
+            len: PhantomData,
+            words: self.words.iter(),
+            word: 0,
+            base: 0,
+        }
+    }
+
+    fn zip_words<F>(&self, other: &Self, f: F) -> Self
+        where F: Fn(u64, u64) -> u64 {
+        let words = self.words.iter().zip(other.words.iter())
+            .map(|(&a, &b)| f(a, b))
+            .collect::<Vec<_>>();
+        IxSet { len: self.len, words: words.into_boxed_slice() }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_words(other, |a, b| a & !b)
     }
 }
 
-impl<'l> Deref for IxRange<'l> {
-    type Target = Range<usize>;
-    fn deref(&self) -> &Self::Target {
-        &self.inner
+impl<'l> fmt::Debug for IxSet<'l> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("IxSet(")?;
+        f.debug_set().entries(self.iter().map(Ix::into_inner)).finish()?;
+        f.write_str(")")
     }
 }
 
-impl<'l> DerefMut for IxRange<'l> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.inner
+impl<'l> Extend<Ix<'l>> for IxSet<'l> {
+    fn extend<I: IntoIterator<Item = Ix<'l>>>(&mut self, iter: I) {
+        for index in iter {
+            self.insert(index);
+        }
     }
 }
 
-impl<'l> Iterator for IxRange<'l> {
+impl<'a, 'l> IntoIterator for &'a IxSet<'l> {
+    type Item = Ix<'l>;
+    type IntoIter = IxSetIter<'a, 'l>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the indices of an `IxSet`.
+#[derive(Clone)]
+pub struct IxSetIter<'a, 'l> {
+    len: PhantomData<Val<'l, usize>>,
+    words: slice::Iter<'a, u64>,
+    word: u64,
+    base: usize,
+}
+
+impl<'a, 'l> Iterator for IxSetIter<'a, 'l> {
     type Item = Ix<'l>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        (**self).next().map(|i| unsafe { Ix::from_raw(i) })
+        while self.word == 0 {
+            self.word = *self.words.next()?;
+            self.base += BITS;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        // bits at or above 'l are never set
+        Some(unsafe { Ix::from_raw(self.base - BITS + bit) })
     }
+}
 
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (**self).size_hint()
+/// A map from indices below `'l` to values of type `V`, stored densely.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct IxMap<'l, V> {
+    inner: BoxedSl<'l, Option<V>>,
+    count: usize,
+}
+
+impl<'l, V> IxMap<'l, V> {
+    /// Creates an empty map.
+    pub fn new(len: Val<'l, usize>) -> Self {
+        let vec: Vec<_> = IxRange::new_full(len).map(|_| None).collect();
+        IxMap {
+            inner: unsafe { BoxedSl::from_raw(vec.into_boxed_slice()) },
+            count: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> Val<'l, usize> {
+        self.inner.len()
+    }
+
+    /// The number of entries in the map.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn contains_key(&self, index: Ix<'l>) -> bool {
+        self.inner[index].is_some()
+    }
+
+    pub fn get(&self, index: Ix<'l>) -> Option<&V> {
+        self.inner[index].as_ref()
+    }
+
+    pub fn get_mut(&mut self, index: Ix<'l>) -> Option<&mut V> {
+        self.inner[index].as_mut()
+    }
+
+    /// Returns the previous value, if any.
+    pub fn insert(&mut self, index: Ix<'l>, value: V) -> Option<V> {
+        let old = self.inner[index].replace(value);
+        if old.is_none() {
+            self.count += 1;
+        }
+        old
+    }
+
+    pub fn remove(&mut self, index: Ix<'l>) -> Option<V> {
+        let old = self.inner[index].take();
+        if old.is_some() {
+            self.count -= 1;
+        }
+        old
+    }
+
+    /// The set of indices that have an entry.
+    pub fn keys(&self) -> IxSet<'l> {
+        let mut set = IxSet::new(self.capacity());
+        set.extend(self.iter().map(|(i, _)| i));
+        set
+    }
+
+    /// Iterate over the entries in ascending order of index.
+    pub fn iter<'a>(&'a self) -> IxMapIter<'a, 'l, V> {
+        IxMapIter { inner: self.inner.iter_enumerated() }
     }
 }
 
-impl<'l> DoubleEndedIterator for IxRange<'l> {
-    fn next_back(&mut self) -> Option<Self::Item> {
-        (**self).next_back().map(|i| unsafe { Ix::from_raw(i) })
+impl<'l, V: fmt::Debug> fmt::Debug for IxMap<'l, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("IxMap(")?;
+        f.debug_map()
+            .entries(self.iter().map(|(i, v)| (i.into_inner(), v)))
+            .finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'a, 'l, V> IntoIterator for &'a IxMap<'l, V> {
+    type Item = (Ix<'l>, &'a V);
+    type IntoIter = IxMapIter<'a, 'l, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of an `IxMap`.
+pub struct IxMapIter<'a, 'l, V: 'a> {
+    inner: IterEnumerated<'a, 'l, Option<V>>,
+}
+
+impl<'a, 'l, V> Clone for IxMapIter<'a, 'l, V> {
+    fn clone(&self) -> Self {
+        IxMapIter { inner: self.inner.clone() }
+    }
+}
+
+impl<'a, 'l, V> Iterator for IxMapIter<'a, 'l, V> {
+    type Item = (Ix<'l>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (i, v) in &mut self.inner {
+            if let Some(ref v) = *v {
+                return Some((i, v));
+            }
+        }
+        None
     }
 }
 
@@ -399,4 +2909,468 @@ mod tests {
             }
         })
     }
+
+    #[test]
+    fn prev_next() {
+        imprint(3, |n| {
+            let vec = vec!['a', 'b', 'c'];
+            let slice = Sl::from_slice(&vec, n).unwrap();
+            let mut i = Some(Ix::last(n, arith::decide_nonzero(&n).unwrap()));
+            let mut backwards = Vec::new();
+            while let Some(j) = i {
+                backwards.push(slice[j]);
+                i = j.prev();
+            }
+            assert_eq!(backwards, ['c', 'b', 'a']);
+            let first = Ix::try_new(0, n).unwrap();
+            assert_eq!(first.next(n).map(|i| slice[i]), Some('b'));
+            assert_eq!(Ix::try_new(2, n).unwrap().next(n), None);
+        })
+    }
+
+    #[test]
+    fn split_at() {
+        imprint(5, |n| {
+            let mut a = BoxedSl::from_boxed_slice(
+                vec![1, 2, 3, 4, 5].into_boxed_slice(), n).unwrap();
+            let mid = Ix::try_new(2, n).unwrap();
+            a.split_at_mut(mid, |m, mut left, k, mut right, sum| {
+                assert_eq!((*m, *k), (2, 3));
+                for i in IxRange::new_full(m) {
+                    left[i] *= 10;
+                    assert_eq!(i.lift_left(sum).split(m, sum), Ok(i));
+                }
+                for j in IxRange::new_full(k) {
+                    right[j] *= 100;
+                    assert_eq!(j.lift_right(m, sum).split(m, sum), Err(j));
+                }
+            });
+            assert_eq!(&**a, &[10, 20, 300, 400, 500]);
+            a.split_at(mid, |m, left, k, right, sum| {
+                assert_eq!(left.into_slice(m), &[10, 20]);
+                assert_eq!(right.into_slice(k), &[300, 400, 500]);
+                let last = Ix::last(k, arith::decide_nonzero(&k).unwrap());
+                assert_eq!(last.lift_right(m, sum),
+                           Ix::try_new(4, n).unwrap());
+            });
+        })
+    }
+
+    #[test]
+    fn iterators() {
+        imprint(4, |n| {
+            let mut a = BoxedSl::new(n, 0);
+            for (i, x) in a.iter_enumerated_mut() {
+                *x = *i * 2;
+            }
+            let indices: Vec<_> = a.iter_enumerated()
+                .filter(|&(_, &x)| x > 2)
+                .map(|(i, _)| i)
+                .collect();
+            assert_eq!(indices.iter().map(|&i| a[i]).collect::<Vec<_>>(),
+                       [4, 6]);
+            {
+                let mut sl = a.as_mut_sl();
+                for x in sl.iter_mut(n) {
+                    *x += 1;
+                }
+                assert_eq!(sl.iter(n).sum::<usize>(), 16);
+                let (i, x) = sl.iter_enumerated(n).next_back().unwrap();
+                assert_eq!((*i, *x), (3, 7));
+            }
+            assert_eq!(a.as_sl().iter(n).len(), 4);
+            assert_eq!((&a).into_iter().count(), 4);
+            assert_eq!(a.into_iter().collect::<Vec<_>>(), [1, 3, 5, 7]);
+        })
+    }
+
+    #[test]
+    fn sl_len() {
+        imprint(3, |n| {
+            let a = BoxedSl::from_boxed_slice(
+                vec![1, 2, 3].into_boxed_slice(), n).unwrap();
+            let b = vec![1, 2, 3];
+            let x = a.as_sl_len();
+            let y = SlLen::from_slice(&b, n).unwrap();
+            assert_eq!(x, y);
+            assert_eq!(format!("{:?}", x), "SlLen([1, 2, 3])");
+            assert_eq!(x.iter().sum::<i32>(), 6);
+            assert_eq!(&(*x)[1..], &[2, 3]);
+            let i = Ix::try_new(2, n).unwrap();
+            assert_eq!(x[i], 3);
+            assert_eq!(Sl::from(x)[i], y.as_sl()[i]);
+            assert_eq!(y.into_iter().count(), *y.len());
+        })
+    }
+
+    #[test]
+    fn branded_vec() {
+        let log = BrandedVec::new();
+        log.push("a", |log, lt0| {
+            let a = Ix::new(Val::default(), lt0);
+            let len1 = log.len();
+            log.push("b", |log, lt1| {
+                let b = Ix::new(len1, lt1);
+                let a = a.convert(LessEqual::from(lt1));
+                assert_eq!((log[a], log[b]), ("a", "b"));
+                let nz = arith::decide_nonzero(&log.len()).unwrap();
+                log.pop(nz, |log, b_, _| {
+                    assert_eq!(b_, "b");
+                    assert_eq!(&*log, &["a"]);
+                    let zero: Val<usize> = Val::default();
+                    let le = arith::decide((&zero, &log.len()))
+                        .into_result().unwrap();
+                    assert!(log.truncate(zero, le).is_empty());
+                })
+            })
+        })
+    }
+
+    #[test]
+    fn matrix() {
+        imprint(2, |rows| { imprint(3, |cols| {
+            let mut m = Matrix::from_fn(rows, cols, |i, j| *i * 10 + *j)
+                .unwrap();
+            assert_eq!(format!("{:?}", m),
+                       "Matrix([[0, 1, 2], [10, 11, 12]])");
+            let i = Ix::try_new(1, rows).unwrap();
+            let j = Ix::try_new(2, cols).unwrap();
+            assert_eq!(m[(i, j)], 12);
+            assert_eq!(m.view().transpose()[(j, i)], 12);
+            assert_eq!(m.row(i).into_slice(cols), &[10, 11, 12]);
+            let col: Vec<_> = m.view().col(j).iter().cloned().collect();
+            assert_eq!(col, [2, 12]);
+            assert_eq!(m.view().iter_cols().count(), 3);
+            {
+                let mut t = m.view_mut().transpose();
+                t[(j, i)] = 99;
+                t.row_mut(j)[i] += 1;
+                let mut c = t.col_mut(i);
+                c[j] += 1;
+                for mut col in t.iter_cols_mut() {
+                    col[j] += 1000;
+                }
+                assert_eq!(t.iter_rows_mut().rev().count(), 3);
+            }
+            assert_eq!(m[(i, j)], 1101);
+            m.into_boxed_sl(|flat, product| {
+                assert_eq!(flat[Ix::flatten(i, j, cols, product)], 1101);
+                assert_eq!(&**flat, &[0, 1, 1002, 10, 11, 1101]);
+            });
+        }) })
+    }
+
+    #[test]
+    fn strided() {
+        imprint(3, |rows| { imprint(4, |cols| {
+            let mut m = Matrix::from_fn(rows, cols, |i, j| *i * 10 + *j)
+                .unwrap();
+            let one = Ix::try_new(1, rows).unwrap();
+            let two = Ix::try_new(2, cols).unwrap();
+            m.as_strided().split_rows(one, |_, bottom, _| {
+                bottom.split_cols(two, |_, block, _| {
+                    assert_eq!(*block.stride(), 4);
+                    assert_eq!(format!("{:?}", block),
+                               "Strided([[12, 13], [22, 23]])");
+                    let i = Ix::try_new(1, block.rows()).unwrap();
+                    let j = Ix::try_new(0, block.cols()).unwrap();
+                    assert_eq!(block[(i, j)], 22);
+                    assert_eq!(block.row(i).into_slice(block.cols()),
+                               &[22, 23]);
+                    let col: Vec<_> = block.col(j).iter().cloned().collect();
+                    assert_eq!(col, [12, 22]);
+                })
+            });
+            m.as_strided_mut().split_cols_mut(two, |mut left, mut right, _| {
+                let i = Ix::try_new(2, rows).unwrap();
+                let j = Ix::try_new(1, right.cols()).unwrap();
+                right[(i, j)] = 0;
+                let j = Ix::try_new(0, left.cols()).unwrap();
+                left.col_mut(j)[i] = 0;
+            });
+            assert_eq!(format!("{:?}", m.view().iter_rows().last()),
+                       "Some([0, 21, 22, 0])");
+        }) })
+    }
+
+    #[test]
+    fn strided_new() {
+        let buf = [1, 2, 3, 4, 5, 6];
+        imprint(buf.len(), |len| {
+            let sl = Sl::from_slice(&buf, len).unwrap();
+            imprint(2, |rows| { imprint(2, |cols| { imprint(3, |stride| {
+                let le = arith::decide((&cols, &stride))
+                    .into_result().unwrap();
+                let product = arith::mul(&rows, &stride, |l, product| {
+                    let eq = arith::partial_equal(&l, &len).unwrap();
+                    Product::from_equal(product.into_equal().trans(eq))
+                }).unwrap();
+                let s = Strided::new(sl, rows, cols, stride, le, product);
+                assert_eq!(format!("{:?}", s), "Strided([[1, 2], [4, 5]])");
+            }) }) })
+        })
+    }
+
+    #[test]
+    fn mapping() {
+        #[derive(Clone, Copy)]
+        enum Color { Red, Green, Blue }
+
+        imprint(3, |len| {
+            let ix: Vec<_> = IxRange::new_full(len).collect();
+            let color = |c| match c {
+                Color::Red => ix[0],
+                Color::Green => ix[1],
+                Color::Blue => ix[2],
+            };
+            let mut m = Mapping::new(color, BoxedSl::new(len, 0));
+            m[Color::Green] += 1;
+            m[Color::Blue] += 2;
+            assert_eq!(&***m.store(), &[0, 1, 2]);
+            assert_eq!(m[Color::Red], 0);
+
+            let (_, store) = m.into_inner();
+            let mut m = Mapping::new(|i| Ix::try_new(i, len), store);
+            *m.get_mut(0).unwrap() += 3;
+            assert_eq!(m.get(0), Some(&3));
+            assert_eq!(m.get(3), None);
+        });
+
+        fn swap<'r, 'c>((j, i): (Ix<'c>, Ix<'r>)) -> (Ix<'r>, Ix<'c>) {
+            (i, j)
+        }
+
+        imprint(2, |rows| { imprint(3, |cols| {
+            arith::mul(&rows, &cols, |len, product| {
+                let flat = BoxedSl::from_boxed_slice(
+                    (0..6).collect::<Vec<_>>().into_boxed_slice(), len)
+                    .unwrap();
+                let m = Mapping::new(swap.then(RowMajor::new(cols, product)),
+                                     flat);
+                let i = Ix::try_new(1, rows).unwrap();
+                let j = Ix::try_new(2, cols).unwrap();
+                assert_eq!(m[(j, i)], 5);
+            }).unwrap();
+        }) })
+    }
+
+    #[test]
+    fn binary_search() {
+        let v = vec![1, 3, 3, 5, 7];
+        imprint(v.len(), |len| {
+            let b = BoxedSl::from_boxed_slice(v.into_boxed_slice(), len)
+                .unwrap();
+            let i = b.binary_search(&5).unwrap();
+            assert_eq!(b[i], 5);
+            let pos = b.binary_search(&4).unwrap_err();
+            assert_eq!(*pos, 3);
+            assert_eq!(pos.to_ix(len), Some(i));
+            let below: Vec<_> = pos.range_to().map(|i| b[i]).collect();
+            assert_eq!(below, [1, 3, 3]);
+            let n = pos.with(|p, le| IxRange::new_with(1, p, le).count());
+            assert_eq!(n, 2);
+            assert_eq!(pos.range_from(len).count(), 2);
+            let end = b.binary_search(&8).unwrap_err();
+            assert_eq!(end, InsertPos::end(len));
+            assert_eq!(end.to_ix(len), None);
+            assert_eq!(end.range_from(len).count(), 0);
+            let pp = b.partition_point(|&x| x < 3);
+            assert_eq!(format!("{:?}", pp), "InsertPos(1)");
+            assert_eq!(b.binary_search_by_key(&10, |&x| x * 2),
+                       Ok(Ix::try_new(3, len).unwrap()));
+            // results that std doesn't keep in bounds are clamped
+            assert_eq!(search_result(Ok(5), len), Err(InsertPos::end(len)));
+            assert_eq!(insert_pos(6, len), InsertPos::end(len));
+        })
+    }
+
+    #[test]
+    fn sorted() {
+        let v = vec![5, 1, 4, 2, 3];
+        imprint(v.len(), |len| {
+            let mut b = BoxedSl::from_boxed_slice(v.into_boxed_slice(), len)
+                .unwrap();
+            assert!(b.as_sl_len().check_sorted().is_none());
+            {
+                let sorted = b.sort();
+                assert_eq!(format!("{:?}", sorted),
+                           "Sorted([1, 2, 3, 4, 5])");
+                assert_eq!(*sorted.search(&4).unwrap(), 3);
+                assert_eq!(*sorted.search(&0).unwrap_err(), 0);
+            }
+            let sorted = b.sort_by_key(|&x| cmp::Reverse(x));
+            assert_eq!(sorted.into_slice(), &[5, 4, 3, 2, 1]);
+            let i = sorted.search_by_key(&cmp::Reverse(2)).unwrap();
+            assert_eq!(sorted[i], 2);
+            assert!(b.as_sl_len().check_sorted_by_key(|&x| -x).is_some());
+            let sorted = b.as_sl_len().check_sorted_by_key(|&x| x % 2 == 0);
+            assert!(sorted.is_none());
+        })
+    }
+
+    #[test]
+    fn permutation() {
+        let v = vec![30, 10, 20, 40];
+        imprint(v.len(), |len| {
+            let mut a = BoxedSl::from_boxed_slice(v.into_boxed_slice(), len)
+                .unwrap();
+            let mut b = BoxedSl::new(len, 0);
+            for (i, x) in b.iter_enumerated_mut() {
+                *x = *i;
+            }
+            let p = Permutation::argsort(a.as_sl_len());
+            assert_eq!(format!("{:?}", p), "Permutation([1, 2, 0, 3])");
+            p.permute(a.as_mut_sl());
+            p.permute(b.as_mut_sl());
+            assert_eq!(&**a, &[10, 20, 30, 40]);
+            assert_eq!(&**b, &[1, 2, 0, 3]);
+            let q = p.inverse();
+            assert_eq!(p.compose(&q), Permutation::identity(len));
+            q.permute(a.as_mut_sl());
+            assert_eq!(&**a, &[30, 10, 20, 40]);
+            let i = Ix::try_new(2, len).unwrap();
+            assert_eq!(format!("{:?}", p.apply(i)), "Ix(0)");
+            assert_eq!(q[p[i]], i);
+
+            let dup = BoxedSl::new(len, i);
+            assert!(Permutation::from_boxed_sl(dup).is_err());
+            let ok = Permutation::from_boxed_sl(q.into_boxed_sl()).unwrap();
+            assert_eq!(ok.compose(&p), Permutation::identity(len));
+        })
+    }
+
+    #[test]
+    fn zip() {
+        imprint(4, |n| {
+            let a = BoxedSl::new(n, 1);
+            let b = a.clone().map(|x| x as f64 * 0.5);
+            let mut c = a.zip_with(&b, |&x, &y| x as f64 + y);
+            assert_eq!(&**c, &[1.5; 4]);
+            for (z, &x) in super::zip_mut(c.as_mut_sl(), a.as_sl(), n) {
+                *z -= x as f64;
+            }
+            let sum: f64 = super::zip3(a.as_sl(), b.as_sl(), c.as_sl(), n)
+                .map(|(&x, &y, &z)| x as f64 + y + z)
+                .sum();
+            assert_eq!(sum, 8.0);
+            let mut d = BoxedSl::new(n, 0.0);
+            for_each_indexed(n, |i| d[i] = b[i] * c[i]);
+            let dot: f64 = super::zip(d.as_sl(), a.as_sl(), n).rev()
+                .map(|(&x, &y)| x * y as f64)
+                .sum();
+            assert_eq!(dot, 1.0);
+        })
+    }
+
+    #[test]
+    fn chunks() {
+        let v: Vec<i32> = (0 .. 7).collect();
+        imprint(v.len(), |n| {
+            let mut b = BoxedSl::from_boxed_slice(v.into_boxed_slice(), n)
+                .unwrap();
+            imprint(3, |k| {
+                let nonzero = arith::decide_nonzero(&k).unwrap();
+                let last = Ix::last(k, nonzero);
+                let lasts: Vec<_> = b.chunks_exact(k, nonzero)
+                    .map(|c| c[last])
+                    .collect();
+                assert_eq!(lasts, [2, 5]);
+                let chunks = b.chunks_exact(k, nonzero);
+                assert_eq!(chunks.len(), 2);
+                chunks.with_remainder(|r, rem| {
+                    assert_eq!(rem.into_slice(r), &[6]);
+                });
+                let sums: Vec<_> = b.windows(k, nonzero)
+                    .map(|w| w.iter(k).sum::<i32>())
+                    .collect();
+                assert_eq!(sums, [3, 6, 9, 12, 15]);
+                let mut chunks = b.chunks_exact_mut(k, nonzero);
+                for mut c in &mut chunks {
+                    c[last] = 0;
+                }
+                chunks.into_remainder(|r, mut rem| {
+                    for x in rem.iter_mut(r) {
+                        *x = -1;
+                    }
+                });
+                assert_eq!(&**b, &[0, 1, 0, 3, 4, 0, -1]);
+            })
+        })
+    }
+
+    #[test]
+    fn ix_set() {
+        imprint(130, |n| {
+            let ix = |i| Ix::try_new(i, n).unwrap();
+            let mut a = IxSet::new(n);
+            assert!(a.is_empty());
+            assert!(a.insert(ix(3)));
+            assert!(!a.insert(ix(3)));
+            a.extend(vec![ix(64), ix(129)]);
+            assert!(a.contains(ix(64)));
+            assert!(!a.contains(ix(63)));
+            assert_eq!(a.count(), 3);
+            assert_eq!(format!("{:?}", a), "IxSet({3, 64, 129})");
+            let mut b = IxSet::new(n);
+            b.extend(vec![ix(0), ix(64)]);
+            assert_eq!(format!("{:?}", a.union(&b)),
+                       "IxSet({0, 3, 64, 129})");
+            assert_eq!(format!("{:?}", a.intersection(&b)), "IxSet({64})");
+            assert_eq!(format!("{:?}", a.difference(&b)), "IxSet({3, 129})");
+            assert!(a.remove(ix(129)));
+            assert!(!a.remove(ix(129)));
+            assert_eq!(IxSet::full(n).count(), 130);
+            assert_eq!(IxSet::full(n).difference(&a).count(), 128);
+        })
+    }
+
+    #[test]
+    fn ix_map() {
+        imprint(5, |n| {
+            let ix = |i| Ix::try_new(i, n).unwrap();
+            let mut pred = IxMap::new(n);
+            assert_eq!(pred.insert(ix(1), ix(0)), None);
+            assert_eq!(pred.insert(ix(4), ix(1)), None);
+            assert_eq!(pred.insert(ix(4), ix(2)), Some(ix(1)));
+            assert_eq!(pred.count(), 2);
+            assert!(pred.contains_key(ix(4)));
+            assert_eq!(pred.get(ix(0)), None);
+            *pred.get_mut(ix(1)).unwrap() = ix(3);
+            assert_eq!(format!("{:?}", pred), "IxMap({1: Ix(3), 4: Ix(2)})");
+            assert_eq!(format!("{:?}", pred.keys()), "IxSet({1, 4})");
+            assert_eq!(pred.remove(ix(1)), Some(ix(3)));
+            assert_eq!(pred.iter().count(), 1);
+            assert!(!pred.is_empty());
+        })
+    }
+
+    #[test]
+    fn flatten() {
+        imprint(3, |rows| { imprint(4, |cols| {
+            arith::mul(&rows, &cols, |n, prod| {
+                let mut a = BoxedSl::new(n, (0, 0));
+                for i in IxRange::new_full(rows) {
+                    for j in IxRange::new_full(cols) {
+                        a[Ix::flatten(i, j, cols, prod)] = (*i, *j);
+                    }
+                }
+                assert_eq!(a[Ix::try_new(6, n).unwrap()], (1, 2));
+            }).unwrap()
+        }) })
+    }
+
+    #[test]
+    fn wrap() {
+        imprint(5, |n| {
+            let nz = arith::decide_nonzero(&n).unwrap();
+            let ring = BoxedSl::from_boxed_slice(
+                vec!['a', 'b', 'c', 'd', 'e'].into_boxed_slice(), n).unwrap();
+            assert_eq!(ring[Ix::wrap(12, n, nz)], 'c');
+            assert_eq!(ring[Ix::wrap(4, n, nz)], 'e');
+            assert_eq!(ring[Ix::first(nz)], 'a');
+            assert_eq!(ring[Ix::last(n, nz)], 'e');
+            assert_eq!(ring[Ix::clamp(1, n, nz)], 'b');
+            assert_eq!(ring[Ix::clamp(99, n, nz)], 'e');
+        })
+    }
 }
@@ -14,8 +14,10 @@
 extern crate num;
 extern crate num_iter;
 
+pub mod arena;
 pub mod arith;
 pub mod ix;
+pub mod map;
 
 use std::borrow::Borrow;
 use std::cell::Cell;
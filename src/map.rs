@@ -0,0 +1,421 @@
+//! Hash maps whose lookups return evidence that a key is present.
+//!
+//! A `BrandedMap<'m, K, V>` is branded by its set of keys.  Checking
+//! membership yields a `Key<'m, K>`, which can then be used to access the
+//! entry without a second lookup.  Operations that add keys move the map to
+//! a new brand along with a `Subset` proof that carries old keys over, while
+//! operations that remove keys move it to a new brand without one.
+
+use std::{fmt, mem, slice};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::iter::Enumerate;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use super::PhantomInvariantLifetime;
+
+/// Evidence that a key of type `K` is present in a map branded with `'m`.
+pub struct Key<'m, K> {
+    brand: PhantomInvariantLifetime<'m>,
+    key: PhantomData<fn() -> K>,
+    index: usize,
+}
+
+impl<'m, K> Key<'m, K> {
+    unsafe fn from_raw(index: usize) -> Self {
+        Key { brand: PhantomData, key: PhantomData, index }
+    }
+
+    /// Carries the key over to a map with more keys.
+    pub fn convert<'n>(self, _: Subset<'m, 'n>) -> Key<'n, K> {
+        unsafe { Key::from_raw(self.index) }
+    }
+}
+
+impl<'m, K> Clone for Key<'m, K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'m, K> Copy for Key<'m, K> {}
+
+impl<'m, K> PartialEq for Key<'m, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<'m, K> Eq for Key<'m, K> {}
+
+impl<'m, K> Hash for Key<'m, K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state)
+    }
+}
+
+impl<'m, K> fmt::Debug for Key<'m, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Key(")?;
+        self.index.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+/// Evidence that every key of `'m` is also a key of `'n`.
+#[derive(Clone, Copy)]
+pub struct Subset<'m, 'n> {
+    small: PhantomInvariantLifetime<'m>,
+    large: PhantomInvariantLifetime<'n>,
+}
+
+impl<'m, 'n> Subset<'m, 'n> {
+    unsafe fn conjure() -> Self {
+        Subset { small: PhantomData, large: PhantomData }
+    }
+
+    pub fn trans<'p>(self, _: Subset<'n, 'p>) -> Subset<'m, 'p> {
+        unsafe { Subset::conjure() }
+    }
+}
+
+impl<'m> Subset<'m, 'm> {
+    pub fn refl() -> Self {
+        unsafe { Subset::conjure() }
+    }
+}
+
+impl<'m, 'n> fmt::Debug for Subset<'m, 'n> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Subset")
+    }
+}
+
+/// Reports a key whose `Hash` or `Eq` changed while it was in a map.
+fn broken_key() -> ! {
+    panic!("BrandedMap: the Hash or Eq of a key changed while in the map")
+}
+
+/// A hash map branded with its set of keys.
+///
+/// ## Assumptions
+///
+/// As with `HashMap`, the `Hash` and `Eq` of a key must not change while
+/// the key is in the map, e.g. through a `Cell`.  This can't be enforced,
+/// and a key that breaks it can make `contains` return a `Key` for the
+/// wrong entry, or one past the end after a removal.  Accessing an entry
+/// through a `Key` therefore never hashes again, but still compares the
+/// index against the length, and panics rather than reading out of bounds.
+pub struct BrandedMap<'m, K, V> {
+    brand: PhantomInvariantLifetime<'m>,
+    entries: Vec<(K, V)>,
+    indices: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq, V> BrandedMap<'static, K, V> {
+    /// Creates an empty map.  All empty maps share the brand `'static`.
+    pub fn new() -> Self {
+        BrandedMap {
+            brand: PhantomData,
+            entries: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Default for BrandedMap<'static, K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'m, K: Hash + Eq, V> BrandedMap<'m, K, V> {
+    unsafe fn from_raw<'n>(entries: Vec<(K, V)>, indices: HashMap<K, usize>)
+                           -> BrandedMap<'n, K, V> {
+        BrandedMap { brand: PhantomData, entries, indices }
+    }
+
+    pub fn from_hash_map<F, R>(map: HashMap<K, V>, callback: F) -> R
+        where K: Clone,
+              F: for<'n> FnOnce(BrandedMap<'n, K, V>) -> R {
+        let mut indices = HashMap::with_capacity(map.len());
+        let entries: Vec<_> = map.into_iter().collect();
+        for (i, entry) in entries.iter().enumerate() {
+            indices.insert(entry.0.clone(), i);
+        }
+        callback(unsafe { BrandedMap::from_raw(entries, indices) })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Checks whether the key is present.  If it is, evidence of its
+    /// presence is returned.
+    pub fn contains<Q>(&self, key: &Q) -> Option<Key<'m, K>>
+        where K: Borrow<Q>, Q: ?Sized + Hash + Eq {
+        self.indices.get(key).map(|&i| unsafe { Key::from_raw(i) })
+    }
+
+    fn entry(&self, key: Key<'m, K>) -> &(K, V) {
+        match self.entries.get(key.index) {
+            Some(entry) => entry,
+            None => broken_key(),
+        }
+    }
+
+    fn entry_mut(&mut self, key: Key<'m, K>) -> &mut (K, V) {
+        match self.entries.get_mut(key.index) {
+            Some(entry) => entry,
+            None => broken_key(),
+        }
+    }
+
+    pub fn key(&self, key: Key<'m, K>) -> &K {
+        &self.entry(key).0
+    }
+
+    pub fn get(&self, key: Key<'m, K>) -> &V {
+        &self.entry(key).1
+    }
+
+    pub fn get_mut(&mut self, key: Key<'m, K>) -> &mut V {
+        &mut self.entry_mut(key).1
+    }
+
+    /// Replaces the value of an existing key, which leaves the brand
+    /// unchanged.
+    pub fn replace(&mut self, key: Key<'m, K>, value: V) -> V {
+        mem::replace(self.get_mut(key), value)
+    }
+
+    /// Inserts a key, replacing its value if it was already present.  The
+    /// callback receives the map under a new brand, the inserted key, and
+    /// evidence that every old key is still present.
+    pub fn insert<F, R>(self, key: K, value: V, callback: F) -> R
+        where K: Clone,
+              F: for<'n> FnOnce(BrandedMap<'n, K, V>,
+                                Key<'n, K>,
+                                Subset<'m, 'n>) -> R {
+        let mut entries = self.entries;
+        let mut indices = self.indices;
+        let index = match indices.get(&key) {
+            Some(&i) => {
+                entries[i].1 = value;
+                i
+            }
+            None => {
+                let i = entries.len();
+                indices.insert(key.clone(), i);
+                entries.push((key, value));
+                i
+            }
+        };
+        unsafe {
+            callback(BrandedMap::from_raw(entries, indices),
+                     Key::from_raw(index),
+                     Subset::conjure())
+        }
+    }
+
+    /// Removes a key.  The callback receives the map under a new brand,
+    /// which invalidates every existing `Key`, along with the removed entry.
+    pub fn remove<F, R>(self, key: Key<'m, K>, callback: F) -> R
+        where F: for<'n> FnOnce(BrandedMap<'n, K, V>, (K, V)) -> R {
+        let mut entries = self.entries;
+        let mut indices = self.indices;
+        if key.index >= entries.len() {
+            broken_key()
+        }
+        let entry = entries.swap_remove(key.index);
+        if indices.remove(&entry.0).is_none() {
+            broken_key()
+        }
+        if let Some(moved) = entries.get(key.index) {
+            match indices.get_mut(&moved.0) {
+                Some(i) => *i = key.index,
+                None => broken_key(),
+            }
+        }
+        callback(unsafe { BrandedMap::from_raw(entries, indices) }, entry)
+    }
+
+    /// Keys in an unspecified order.
+    pub fn keys(&self) -> Keys<'m, K> {
+        Keys {
+            brand: PhantomData,
+            key: PhantomData,
+            range: 0 .. self.entries.len(),
+        }
+    }
+
+    pub fn iter<'a>(&'a self) -> Iter<'a, 'm, K, V> {
+        Iter { brand: PhantomData, inner: self.entries.iter().enumerate() }
+    }
+
+    pub fn into_hash_map(self) -> HashMap<K, V> {
+        self.entries.into_iter().collect()
+    }
+}
+
+impl<'m, K: Clone, V: Clone> Clone for BrandedMap<'m, K, V> {
+    fn clone(&self) -> Self {
+        // the clone has the same keys, so sharing the brand is fine
+        BrandedMap {
+            brand: PhantomData,
+            entries: self.entries.clone(),
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+impl<'m, K: fmt::Debug, V: fmt::Debug> fmt::Debug for BrandedMap<'m, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("BrandedMap(")?;
+        f.debug_map()
+            .entries(self.entries.iter().map(|entry| (&entry.0, &entry.1)))
+            .finish()?;
+        f.write_str(")")
+    }
+}
+
+impl<'m, K: Hash + Eq, V> Index<Key<'m, K>> for BrandedMap<'m, K, V> {
+    type Output = V;
+    fn index(&self, key: Key<'m, K>) -> &Self::Output {
+        self.get(key)
+    }
+}
+
+impl<'m, K: Hash + Eq, V> IndexMut<Key<'m, K>> for BrandedMap<'m, K, V> {
+    fn index_mut(&mut self, key: Key<'m, K>) -> &mut Self::Output {
+        self.get_mut(key)
+    }
+}
+
+/// An iterator over the keys of a `BrandedMap`.
+pub struct Keys<'m, K> {
+    brand: PhantomInvariantLifetime<'m>,
+    key: PhantomData<fn() -> K>,
+    range: ::std::ops::Range<usize>,
+}
+
+impl<'m, K> Clone for Keys<'m, K> {
+    fn clone(&self) -> Self {
+        Keys {
+            brand: PhantomData,
+            key: PhantomData,
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<'m, K> Iterator for Keys<'m, K> {
+    type Item = Key<'m, K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|i| unsafe { Key::from_raw(i) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'m, K> ExactSizeIterator for Keys<'m, K> {}
+
+/// An iterator over the entries of a `BrandedMap` along with their keys.
+pub struct Iter<'a, 'm, K: 'a, V: 'a> {
+    brand: PhantomInvariantLifetime<'m>,
+    inner: Enumerate<slice::Iter<'a, (K, V)>>,
+}
+
+impl<'a, 'm, K, V> Iterator for Iter<'a, 'm, K, V> {
+    type Item = (Key<'m, K>, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(i, entry)| {
+            (unsafe { Key::from_raw(i) }, &entry.0, &entry.1)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, 'm, K, V> ExactSizeIterator for Iter<'a, 'm, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let map = BrandedMap::new();
+        assert!(map.contains("a").is_none());
+        map.insert("a", 1, |map, a, _| {
+            map.insert("b", 2, |mut map, b, sub| {
+                let a = a.convert(sub);
+                map[a] += 10;
+                assert_eq!(map.contains("a"), Some(a));
+                assert_eq!(*map.key(b), "b");
+                assert_eq!(map.replace(b, 3), 2);
+                assert_eq!(map.len(), 2);
+                assert_eq!(map.keys().map(|k| map[k]).sum::<i32>(), 14);
+                map.remove(a, |map, entry| {
+                    assert_eq!(entry, ("a", 11));
+                    let b = map.contains("b").unwrap();
+                    assert_eq!(map[b], 3);
+                    assert!(map.contains("a").is_none());
+                    assert_eq!(format!("{:?}", map), "BrandedMap({\"b\": 3})");
+                })
+            })
+        })
+    }
+
+    // a key whose hash changes behind the map's back is reported rather
+    // than leaving stale indices behind
+    #[test]
+    #[should_panic(expected = "Hash or Eq of a key changed")]
+    fn misbehaving_key() {
+        #[derive(Clone, PartialEq, Eq)]
+        struct Evil(Cell<u32>);
+
+        impl Hash for Evil {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.0.get().hash(state)
+            }
+        }
+
+        BrandedMap::new().insert(Evil(Cell::new(0)), (), |map, _, _| {
+            map.insert(Evil(Cell::new(1)), (), |map, b, _| {
+                map.key(b).0.set(2);
+                map.remove(b, |_, _| ())
+            })
+        })
+    }
+
+    #[test]
+    fn from_hash_map() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert(1, "one");
+        hash_map.insert(2, "two");
+        BrandedMap::from_hash_map(hash_map.clone(), |map| {
+            let mut found: Vec<_> = map.iter()
+                .map(|(key, &k, &v)| {
+                    assert_eq!(map[key], v);
+                    (k, v)
+                })
+                .collect();
+            found.sort();
+            assert_eq!(found, [(1, "one"), (2, "two")]);
+            assert_eq!(map.into_hash_map(), hash_map);
+        })
+    }
+}